@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+pub const CACHE_DIR: &str = ".twios-cache";
+
+/// A response of the GitHub API stored on disk.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Entry {
+    pub etag: Option<String>,
+    /// The UNIX timestamp of the response.
+    pub fetched_at: i64,
+    pub body: String,
+}
+
+/// Responses stored in a directory by their key, e.g. the route of a request.
+/// Without a directory nothing is read or written.
+pub struct Cache {
+    dir: Option<PathBuf>,
+}
+
+/// FNV-1a, which unlike the hasher of the standard library gives the same file names across
+/// Rust versions.
+fn hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl Cache {
+    pub fn new(dir: Option<PathBuf>) -> Cache {
+        Cache { dir }
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{:016x}.json", hash(key))))
+    }
+
+    pub fn read(&self, key: &str) -> Option<Entry> {
+        let contents = fs::read_to_string(self.path(key)?).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// A cache that can't be written only costs requests, so errors are just reported.
+    pub fn write(&self, key: &str, entry: &Entry) {
+        let (dir, path) = match (&self.dir, self.path(key)) {
+            (Some(dir), Some(path)) => (dir, path),
+            _ => return,
+        };
+
+        let result = fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&path, serde_json::to_string(entry).unwrap_or_default()));
+        if let Err(error) = result {
+            eprintln!(
+                "Could not write the cache file {}: {}",
+                path.display(),
+                error
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn it_reads_written_entries() {
+        let dir = env::temp_dir().join(format!("twios-cache-test-{}", std::process::id()));
+        let cache = Cache::new(Some(dir.clone()));
+        let entry = Entry {
+            etag: Some("W/\"abc\"".to_string()),
+            fetched_at: 1704621600,
+            body: "[]".to_string(),
+        };
+
+        assert_eq!(None, cache.read("repos/atom/keyboard-layout/pulls/63"));
+        cache.write("repos/atom/keyboard-layout/pulls/63", &entry);
+        assert_eq!(
+            Some(entry),
+            cache.read("repos/atom/keyboard-layout/pulls/63")
+        );
+        assert_eq!(None, cache.read("repos/atom/keyboard-layout/pulls/64"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn it_does_nothing_without_a_directory() {
+        let cache = Cache::new(None);
+        cache.write(
+            "graphql",
+            &Entry {
+                etag: None,
+                fetched_at: 0,
+                body: "{}".to_string(),
+            },
+        );
+
+        assert_eq!(None, cache.read("graphql"));
+    }
+}
@@ -0,0 +1,199 @@
+use pulldown_cmark::{Event, Parser, Tag};
+
+/// The text between two headings of a markdown document, with the links resolved.
+#[cfg_attr(test, derive(PartialEq, Debug))]
+pub struct Section {
+    pub heading: Option<String>,
+    pub text: String,
+}
+
+/// How a chat app formats the markdown of a report.
+pub struct Syntax {
+    pub link: fn(&str, &str) -> String,
+    pub escape: fn(&str) -> String,
+    pub code: fn(&str) -> String,
+    pub strong: &'static str,
+    pub emphasis: &'static str,
+}
+
+/// Splits a markdown document at its headings, the text before the first one has no heading.
+pub fn sections(markdown: &str, syntax: &Syntax) -> Vec<Section> {
+    let mut sections = vec![];
+    let mut section = Section {
+        heading: None,
+        text: String::new(),
+    };
+    let mut heading: Option<String> = None;
+    let mut link: Option<(String, String)> = None;
+
+    for event in Parser::new(markdown) {
+        let in_heading = heading.is_some();
+        let output = match (&mut link, &mut heading) {
+            (Some((_, text)), _) => text,
+            (None, Some(heading)) => heading,
+            (None, None) => &mut section.text,
+        };
+
+        match event {
+            Event::Start(Tag::Heading(..)) => {
+                if section.heading.is_some() || !section.text.trim().is_empty() {
+                    sections.push(section);
+                }
+                section = Section {
+                    heading: None,
+                    text: String::new(),
+                };
+                heading = Some(String::new());
+            }
+            Event::End(Tag::Heading(..)) => section.heading = heading.take(),
+            Event::Start(Tag::Link(_, url, _)) => link = Some((url.to_string(), String::new())),
+            Event::End(Tag::Link(..)) => {
+                // Headings are plain text, so only the text of their links is kept.
+                match (link.take(), &mut heading) {
+                    (Some((_, text)), Some(heading)) => heading.push_str(&text),
+                    (Some((url, text)), None) => section.text.push_str(&(syntax.link)(&text, &url)),
+                    (None, _) => {}
+                }
+            }
+            Event::Text(content) | Event::Code(content) if in_heading => output.push_str(&content),
+            // Titles like `Support <input>` are parsed as inline HTML.
+            Event::Text(content) | Event::Html(content) => {
+                output.push_str(&(syntax.escape)(&content))
+            }
+            Event::Code(content) => output.push_str(&(syntax.code)(&content)),
+            Event::Start(Tag::Item) => output.push_str("• "),
+            Event::Start(Tag::Strong) | Event::End(Tag::Strong) => output.push_str(syntax.strong),
+            Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) => {
+                output.push_str(syntax.emphasis)
+            }
+            Event::SoftBreak | Event::HardBreak | Event::End(Tag::Item | Tag::List(_)) => {
+                output.push('\n')
+            }
+            Event::End(Tag::Paragraph) => output.push_str("\n\n"),
+            _ => {}
+        }
+    }
+
+    if section.heading.is_some() || !section.text.trim().is_empty() {
+        sections.push(section);
+    }
+
+    sections
+        .into_iter()
+        .map(|section| Section {
+            heading: section.heading,
+            text: section.text.trim().to_string(),
+        })
+        .collect()
+}
+
+/// Splits the text between lines into chunks of at most `max_length` characters, lines that are
+/// longer on their own are cut into several ones.
+pub fn split_text(text: &str, max_length: usize) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut chunk = String::new();
+    let mut chunk_length = 0;
+
+    for line in text.trim().lines().flat_map(|line| wrap(line, max_length)) {
+        let line_length = line.chars().count();
+        if !chunk.is_empty() && chunk_length + line_length + 1 > max_length {
+            chunks.push(chunk);
+            chunk = String::new();
+            chunk_length = 0;
+        }
+        if !chunk.is_empty() {
+            chunk.push('\n');
+            chunk_length += 1;
+        }
+        chunk.push_str(&line);
+        chunk_length += line_length;
+    }
+
+    if !chunk.trim().is_empty() {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+fn wrap(line: &str, max_length: usize) -> Vec<String> {
+    let chars = line.chars().collect::<Vec<char>>();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+
+    chars
+        .chunks(max_length.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/// Cuts the text to at most `max_length` characters.
+pub fn truncate(text: &str, max_length: usize) -> String {
+    text.chars().take(max_length).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: Syntax = Syntax {
+        link: |text, url| format!("{} ({})", text, url),
+        escape: |text| text.to_string(),
+        code: |code| format!("`{}`", code),
+        strong: "*",
+        emphasis: "_",
+    };
+
+    #[test]
+    fn it_splits_markdown_into_sections() {
+        let markdown = "Header\n\n## Ember\n\n- [#798](https://github.com/ember-engines/ember-engines/pull/798) **Ember 4** ([@BobrImperator])\n\n## [Rust](https://www.rust-lang.org)\n\nThanks!\n\n[@BobrImperator]: https://github.com/BobrImperator\n";
+
+        assert_eq!(
+            vec![
+                Section {
+                    heading: None,
+                    text: "Header".to_string()
+                },
+                Section {
+                    heading: Some("Ember".to_string()),
+                    text: "• #798 (https://github.com/ember-engines/ember-engines/pull/798) *Ember 4* (@BobrImperator (https://github.com/BobrImperator))".to_string()
+                },
+                Section {
+                    heading: Some("Rust".to_string()),
+                    text: "Thanks!".to_string()
+                },
+            ],
+            sections(markdown, &PLAIN)
+        );
+    }
+
+    #[test]
+    fn it_splits_text_between_lines() {
+        assert_eq!(
+            vec![
+                "aaaa\nbb".to_string(),
+                "cccccc".to_string(),
+                "d".to_string()
+            ],
+            split_text("aaaa\nbb\ncccccc\nd", 7)
+        );
+    }
+
+    #[test]
+    fn it_cuts_lines_longer_than_a_chunk() {
+        assert_eq!(
+            vec![
+                "aaaa".to_string(),
+                "bbbb".to_string(),
+                "bbbb".to_string(),
+                "bb\nc".to_string()
+            ],
+            split_text("aaaa\nbbbbbbbbbb\nc", 4)
+        );
+        assert_eq!(
+            vec!["éééé".to_string(), "é".to_string()],
+            split_text("ééééé", 4)
+        );
+    }
+}
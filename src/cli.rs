@@ -1,32 +1,516 @@
+use crate::error::AppError;
+use crate::schema::{config_schema, find_unknown_keys};
+use chrono::{Datelike, Duration, FixedOffset, Local, NaiveDate, Utc, Weekday};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use serde;
 use serde::Deserialize;
 use serde_json;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::error::Error;
-use std::fs::File;
-use std::io::BufReader;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
 use std::path::Path;
 
-#[derive(Debug)]
-struct Arg(String, String);
+pub const DEFAULT_CONCURRENCY: usize = 3;
+
+const NO_TEAMS_ERROR: &str = "--group-by team needs the teams of a config file.";
+
+#[derive(Debug, Default, Clone, PartialEq, ValueEnum)]
+pub enum SortField {
+    Created,
+    #[default]
+    Repository,
+    Number,
+}
+
+/// What the sections of the report are.
+#[derive(Debug, Default, Clone, PartialEq, ValueEnum)]
+pub enum GroupBy {
+    #[default]
+    Label,
+    /// One section per team of the config, holding the PRs of its members.
+    Team,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Markdown,
+    Html,
+    Json,
+    Email,
+}
+
+impl OutputFormat {
+    /// The extension of the files the reports are written to.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html | OutputFormat::Email => "html",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
+pub enum PublishTarget {
+    Slack,
+    Discord,
+    Mastodon,
+}
+
+/// The format of a config read from stdin, which has no extension to tell it.
+#[derive(Debug, Default, Clone, PartialEq, ValueEnum)]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Toml,
+    Yaml,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, ValueEnum)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Generates a markdown report of the open source contributions of a team.
+#[derive(Parser, Debug)]
+#[command(
+    name = "this-week-in-open-source",
+    version,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    args: Args,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Queries GitHub and writes the report, which is also done without a subcommand.
+    Generate(Args),
+    /// Only checks the config without querying GitHub.
+    Validate {
+        /// Prints the JSON Schema of the config file instead.
+        #[arg(long)]
+        schema: bool,
+        #[command(flatten)]
+        args: Args,
+    },
+    /// Writes a starter config to the first --config-path, twios.json by default.
+    Init(Args),
+    /// Writes the report and posts it, e.g. `publish slack`, `publish discord` or `publish mastodon`.
+    Publish {
+        #[arg(value_enum)]
+        target: PublishTarget,
+        #[command(flatten)]
+        args: Args,
+    },
+    /// Prints the completion script of a shell, e.g. `source <(this-week-in-open-source completions bash)`.
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
 
 #[cfg_attr(test, derive(PartialEq))]
-#[derive(Debug)]
+#[derive(clap::Args, Debug, Default)]
 pub struct Args {
+    /// GitHub logins whose PRs are queried, comma separated.
+    #[arg(long, value_delimiter = ',')]
     pub users: Vec<String>,
+    /// The date PRs were created at, YYYY-MM-DD, FROM..UNTIL or e.g. "last week".
+    #[arg(long, default_value = "", hide_default_value = true)]
     pub date: String,
+    #[arg(skip)]
     pub date_sign: String,
-    pub config_path: String,
+    /// Queries PRs created before --date.
+    #[arg(long, conflicts_with = "after")]
+    pub before: bool,
+    /// Queries PRs created after --date.
+    #[arg(long)]
+    pub after: bool,
+    /// Config files, comma separated or repeated, `-` reads stdin.
+    #[arg(long = "config-path", value_delimiter = ',')]
+    pub config_paths: Vec<String>,
+    /// The format of the config read from stdin with `--config-path=-`.
+    #[arg(long, value_enum, default_value_t)]
+    pub config_format: ConfigFormat,
+    /// Aborts when the config is inconsistent.
+    #[arg(long)]
+    pub strict: bool,
+    /// Skips draft PRs.
+    #[arg(long)]
+    pub exclude_drafts: bool,
+    /// Doesn't mention a not provided config file or report progress.
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Reports each fetched page of search results.
+    #[arg(short, long, conflicts_with = "quiet")]
+    pub verbose: bool,
+    /// Adds a table of contents.
+    #[arg(long)]
+    pub toc: bool,
+    /// Queries PRs created since the last run.
+    #[arg(long)]
+    pub since_last_run: bool,
+    /// Also queries PRs reviewed by the users.
+    #[arg(long)]
+    pub include_reviews: bool,
+    /// Writes the report to the given path.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub output: String,
+    /// How the items of each section are sorted.
+    #[arg(long, value_enum, default_value_t)]
+    pub sort: SortField,
+    #[arg(long, value_enum, default_value_t)]
+    pub order: SortOrder,
+    /// Also credits the co-authors of PRs.
+    #[arg(long)]
+    pub include_coauthors: bool,
+    /// Leaves out the links definitions.
+    #[arg(long)]
+    pub no_definitions: bool,
+    /// Skips users whose PRs could not be fetched.
+    #[arg(long)]
+    pub continue_on_error: bool,
+    /// Queries every PR of the organization instead of the users.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub org: String,
+    /// Writes the report into the given directory.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub out_dir: String,
+    /// Marks open and closed PRs.
+    #[arg(long)]
+    pub mark_state: bool,
+    /// Date windows queried separately, FROM..UNTIL, comma separated.
+    #[arg(long, value_delimiter = ',')]
+    pub windows: Vec<String>,
+    /// Skips PRs up to the numbers of the last run.
+    #[arg(long)]
+    pub since_watermarks: bool,
+    /// The number of concurrent requests.
+    #[arg(long, value_parser = parse_concurrency)]
+    pub concurrency: Option<usize>,
+    /// Only prints the repositories that don't match any label.
+    #[arg(long)]
+    pub list_unknown_repos: bool,
+    /// Adds the repositories that don't match any label to the label, or to pending without one.
+    #[arg(long, num_args = 0..=1, default_missing_value = "", value_name = "LABEL")]
+    pub update_config: Option<String>,
+    /// Skips PRs with fewer added and deleted lines.
+    #[arg(long)]
+    pub min_changes: Option<u64>,
+    /// A command run with the path of each written report.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub exec: String,
+    /// Groups the Unknown section by repository.
+    #[arg(long)]
+    pub group_unknown: bool,
+    /// A file with one user per line.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub users_file: String,
+    /// Adds a leaderboard of the contributors.
+    #[arg(long)]
+    pub leaderboard: bool,
+    /// Writes a report per label into --out-dir.
+    #[arg(long)]
+    pub split_by_label: bool,
+    /// The User-Agent header of the GitHub requests.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub user_agent: String,
+    /// Kept for scripts written before the validate subcommand.
+    #[arg(long, hide = true)]
+    pub validate: bool,
+    /// Also queries issues opened by the users.
+    #[arg(long)]
+    pub include_issues: bool,
+    /// Only queries merged PRs.
+    #[arg(long)]
+    pub only_merged: bool,
+    /// The first day of the queried PRs, YYYY-MM-DD.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub from: String,
+    /// The last day of the queried PRs, YYYY-MM-DD.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub until: String,
+    /// An ISO week, YYYY-Www, or `last`.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub week: String,
+    /// The UTC offset relative dates are resolved in, e.g. +01:00.
+    #[arg(long)]
+    pub timezone: Option<FixedOffset>,
+    /// Prints the report instead of writing it.
+    #[arg(long, visible_alias = "dry-run")]
+    pub stdout: bool,
+    /// The format of the report.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+    /// An RSS or Atom feed file the report is added to as an entry.
+    #[arg(long, default_value = "", hide_default_value = true)]
+    pub feed: String,
+    /// Stores GitHub responses in .twios-cache to save requests when running again.
+    #[arg(long)]
+    pub cache: bool,
+    /// Renders the items fetched by the last run, without any requests.
+    #[arg(long)]
+    pub offline: bool,
+    /// Renders a section per team of the config instead of per label.
+    #[arg(long, value_enum, default_value_t)]
+    pub group_by: GroupBy,
+    /// Logins whose PRs are left out, e.g. of service accounts, comma separated or repeated.
+    #[arg(long = "exclude-user", value_delimiter = ',')]
+    pub exclude_users: Vec<String>,
+    #[arg(skip)]
+    pub schema: bool,
+    #[arg(skip)]
+    pub init: bool,
+    #[arg(skip)]
+    pub completions: Option<Shell>,
+    #[arg(skip)]
+    pub publish: Option<PublishTarget>,
 }
 
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Deserialize, Clone, Debug)]
 pub struct LabelConfig {
     pub name: String,
+    #[serde(default)]
+    pub repos: Vec<String>,
+    #[serde(default)]
+    pub users: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<LabelRule>,
+    #[serde(default)]
+    pub templates: Templates,
+}
+
+/// Labels repositories that don't belong to any label by their GitHub topics and primary language.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct AutoLabels {
+    /// Label names by topic, e.g. `emberjs` to `Ember`.
+    pub topics: BTreeMap<String, String>,
+    /// Label names by primary language, e.g. `Rust` to `Rust`.
+    pub languages: BTreeMap<String, String>,
+}
+
+impl AutoLabels {
+    pub fn is_empty(&self) -> bool {
+        self.topics.is_empty() && self.languages.is_empty()
+    }
+}
+
+/// Matches items whose repository, title and author match all of the given conditions.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct LabelRule {
+    /// `org/repo` where `*` matches any characters, e.g. `ember-cli/*`.
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// A word, or a regular expression when it starts with `re:`, like `exclude_title_patterns`.
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+const BREAK_LINE: &str = r#"
+
+"#;
+
+/// Front matter written at the top of the report, e.g. for Hugo or Jekyll.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct FrontMatter {
+    pub format: FrontMatterFormat,
+    /// `{date}` is replaced, defaults to the `title` of the config.
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontMatterFormat {
+    #[default]
+    Yaml,
+    Toml,
+}
+
+/// The incoming webhook the report is posted to by `publish slack`.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SlackConfig {
+    /// Defaults to the `SLACK_WEBHOOK_URL` environment variable, to keep it out of the config file.
+    pub webhook_url: Option<String>,
+    /// Overrides the channel of the webhook, e.g. `#open-source`.
+    pub channel: Option<String>,
+}
+
+/// The webhook the report is posted to by `publish discord`.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DiscordConfig {
+    /// Defaults to the `DISCORD_WEBHOOK_URL` environment variable, to keep it out of the config file.
+    pub webhook_url: Option<String>,
+}
+
+/// How a login is credited in the report.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug)]
+pub struct UserAlias {
+    /// e.g. the real name or preferred handle.
+    pub name: String,
+    /// Replaces the GitHub profile in the link definitions, e.g. a personal website.
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// The account the report is posted as a thread with by `publish mastodon`.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct MastodonConfig {
+    /// e.g. `https://mastodon.social`.
+    pub instance_url: Option<String>,
+    /// Defaults to the `MASTODON_ACCESS_TOKEN` environment variable, to keep it out of the config file.
+    pub access_token: Option<String>,
+    /// `public`, `unlisted`, `private` or `direct`, defaults to the one of the account.
+    pub visibility: Option<String>,
+    pub max_characters: usize,
+    pub items_per_status: Option<usize>,
+}
+
+impl Default for MastodonConfig {
+    fn default() -> Self {
+        MastodonConfig {
+            instance_url: None,
+            access_token: None,
+            visibility: None,
+            max_characters: 500,
+            items_per_status: None,
+        }
+    }
+}
+
+/// The GitHub Enterprise Server instance queried instead of github.com, and the GitHub App
+/// installation authenticated as.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct GitHubConfig {
+    /// e.g. `https://github.example.com/api/v3`.
+    pub api_url: Option<String>,
+    /// e.g. `https://github.example.com`, defaults to the `api_url` without `/api/v3`.
+    pub web_url: Option<String>,
+    /// Defaults to the `GITHUB_APP_ID` environment variable.
+    pub app_id: Option<u64>,
+    /// Defaults to the `GITHUB_APP_INSTALLATION_ID` environment variable.
+    pub installation_id: Option<u64>,
+    /// The PEM file of the app, its contents default to the `GITHUB_APP_PRIVATE_KEY` environment
+    /// variable.
+    pub private_key_path: Option<String>,
+}
+
+/// A forge the PRs of the report are fetched from.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+/// The GitLab instance whose merge requests are added to the report.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct GitLabConfig {
+    /// e.g. `https://gitlab.example.com` for self-hosted instances.
+    pub base_url: String,
+    /// Defaults to the `GITLAB_TOKEN` environment variable.
+    pub token: Option<String>,
+    /// The GitLab usernames, when they differ from the GitHub logins of `users`.
+    pub users: Vec<String>,
+}
+
+impl Default for GitLabConfig {
+    fn default() -> Self {
+        GitLabConfig {
+            base_url: String::from("https://gitlab.com"),
+            token: None,
+            users: vec![],
+        }
+    }
+}
+
+/// The Gitea, Forgejo or Codeberg instance whose PRs on the repositories are added to the report.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct GiteaConfig {
+    /// e.g. `https://gitea.example.com` for self-hosted instances.
+    pub base_url: String,
+    /// Defaults to the `GITEA_TOKEN` environment variable.
+    pub token: Option<String>,
+    /// The repositories whose PRs are queried, e.g. `simplabs/qunit-dom`.
     pub repos: Vec<String>,
+    /// The usernames on the instance, when they differ from the GitHub logins of `users`.
+    pub users: Vec<String>,
+}
+
+impl Default for GiteaConfig {
+    fn default() -> Self {
+        GiteaConfig {
+            base_url: String::from("https://codeberg.org"),
+            token: None,
+            repos: vec![],
+            users: vec![],
+        }
+    }
+}
+
+/// Handlebars templates replacing how items and section headings are written.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Templates {
+    /// Written for each item, with all of its fields and `credit`, e.g. `- {{issue_title}} ({{credit}})`.
+    pub item: Option<String>,
+    /// Written as the heading of a label section, with `name` and `count`, e.g. `## {{name}}`.
+    pub label: Option<String>,
+}
+
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct OutputStyle {
+    pub definitions_separator: String,
+    pub trailing_newline: bool,
+}
+
+impl Default for OutputStyle {
+    fn default() -> Self {
+        OutputStyle {
+            definitions_separator: BREAK_LINE.to_string(),
+            trailing_newline: true,
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Deserialize, Debug, Default)]
 struct FileConfig {
     labels: Vec<LabelConfig>,
     #[serde(default)]
@@ -37,231 +521,1106 @@ struct FileConfig {
     exclude: Vec<String>,
     #[serde(default)]
     exclude_closed_not_merged: bool,
+    #[serde(default)]
+    thanks_footer: bool,
+    #[serde(default)]
+    output_style: Option<OutputStyle>,
+    #[serde(default)]
+    highlights: Vec<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    rename: HashMap<String, String>,
+    #[serde(default)]
+    aliases: HashMap<String, String>,
+    #[serde(default)]
+    user_aliases: HashMap<String, UserAlias>,
+    #[serde(default)]
+    exclude_title_patterns: Vec<String>,
+    #[serde(default)]
+    tracked_repos: Vec<String>,
+    #[serde(default)]
+    include_issues: bool,
+    #[serde(default)]
+    reviews_heading: Option<String>,
+    #[serde(default)]
+    only_merged: bool,
+    #[serde(default)]
+    skip_drafts: bool,
+    #[serde(default)]
+    skip_bots: bool,
+    #[serde(default)]
+    exclude_users: Vec<String>,
+    #[serde(default)]
+    auto_labels: Option<AutoLabels>,
+    #[serde(default)]
+    pending: Vec<String>,
+    #[serde(default)]
+    output_pattern: Option<String>,
+    #[serde(default)]
+    templates: Option<Templates>,
+    #[serde(default)]
+    front_matter: Option<FrontMatter>,
+    #[serde(default)]
+    slack: Option<SlackConfig>,
+    #[serde(default)]
+    discord: Option<DiscordConfig>,
+    #[serde(default)]
+    mastodon: Option<MastodonConfig>,
+    #[serde(default)]
+    github: Option<GitHubConfig>,
+    #[serde(default)]
+    gitlab: Option<GitLabConfig>,
+    #[serde(default)]
+    gitea: Option<GiteaConfig>,
+    #[serde(default)]
+    sources: Option<Vec<SourceKind>>,
+    #[serde(default)]
+    proxy: Option<String>,
+    #[serde(default)]
+    org: Option<String>,
+    #[serde(default)]
+    teams: BTreeMap<String, Vec<String>>,
+    /// Keys of the file that aren't in the schema, e.g. because of a typo.
+    #[serde(skip)]
+    unknown_keys: Vec<String>,
 }
 
 #[cfg_attr(test, derive(PartialEq))]
-#[derive(Debug)]
+#[derive(Debug, Default, Clone)]
 pub struct AppParams {
     pub exclude_closed_not_merged: bool,
+    pub thanks_footer: bool,
+    pub output_style: OutputStyle,
     pub labels: Vec<LabelConfig>,
     pub header: Vec<String>,
     pub users: Vec<String>,
     pub exclude: Vec<String>,
     pub date: String,
     pub date_sign: String,
-    pub config_path: String,
-}
-
-pub fn args() -> AppParams {
-    let args = process_args(read_args());
-
-    match read_config_from_file(args.config_path.clone()) {
-        Ok(file_config) => AppParams {
-            labels: file_config.labels,
-            header: file_config.header,
-            exclude: file_config.exclude,
-            users: file_config.users,
-            exclude_closed_not_merged: file_config.exclude_closed_not_merged,
-            date: args.date,
-            date_sign: args.date_sign,
-            config_path: args.config_path,
-        },
+    pub config_paths: Vec<String>,
+    pub strict: bool,
+    pub exclude_drafts: bool,
+    /// Leaves out PRs of bots, e.g. renovate or dependabot, `skip_bots` of the config.
+    pub skip_bots: bool,
+    /// Logins that aren't queried and whose PRs are left out, `exclude_users` of the config and `--exclude-user`.
+    pub exclude_users: Vec<String>,
+    /// Labels of unknown repositories by their topics and language, looked up after fetching.
+    pub auto_labels: AutoLabels,
+    pub quiet: bool,
+    pub verbose: bool,
+    pub toc: bool,
+    pub since_last_run: bool,
+    pub include_reviews: bool,
+    pub output: String,
+    pub highlights: Vec<String>,
+    pub sort: SortField,
+    pub order: SortOrder,
+    pub include_coauthors: bool,
+    pub no_definitions: bool,
+    pub continue_on_error: bool,
+    pub org: String,
+    pub out_dir: String,
+    pub mark_state: bool,
+    pub windows: Vec<String>,
+    pub since_watermarks: bool,
+    pub title: Option<String>,
+    pub rename: HashMap<String, String>,
+    pub concurrency: usize,
+    pub aliases: HashMap<String, String>,
+    pub user_aliases: HashMap<String, UserAlias>,
+    pub list_unknown_repos: bool,
+    /// The label `--update-config` adds unknown repositories to, empty for `pending`.
+    pub update_config: Option<String>,
+    /// Repositories that still need a label, `pending` of the config.
+    pub pending: Vec<String>,
+    pub min_changes: Option<u64>,
+    pub exec: String,
+    pub group_unknown: bool,
+    pub exclude_title_patterns: Vec<String>,
+    pub leaderboard: bool,
+    pub split_by_label: bool,
+    pub user_agent: String,
+    pub validate: bool,
+    pub tracked_repos: Vec<String>,
+    pub include_issues: bool,
+    pub reviews_heading: Option<String>,
+    pub only_merged: bool,
+    pub week: String,
+    pub output_pattern: Option<String>,
+    pub stdout: bool,
+    pub format: OutputFormat,
+    pub feed: String,
+    pub cache: bool,
+    pub offline: bool,
+    pub publish: Option<PublishTarget>,
+    pub slack: SlackConfig,
+    pub discord: DiscordConfig,
+    pub mastodon: MastodonConfig,
+    pub github: GitHubConfig,
+    pub gitlab: Option<GitLabConfig>,
+    pub gitea: Option<GiteaConfig>,
+    pub sources: Vec<SourceKind>,
+    pub unknown_keys: Vec<String>,
+    pub schema: bool,
+    pub templates: Templates,
+    pub front_matter: Option<FrontMatter>,
+    /// Used for the requests unless `HTTPS_PROXY` or `HTTP_PROXY` are set.
+    pub proxy: Option<String>,
+    /// The organization whose members are added to the users, `org` of the config.
+    /// Unlike `org`, which searches every PR of an organization, only PRs of its members are queried.
+    pub members_of: Option<String>,
+}
+
+pub fn read_cli_args() -> Result<Args, clap::Error> {
+    parse_cli_args(env::args())
+}
+
+pub fn app_params(args: Args) -> Result<AppParams, AppError> {
+    let file_users = if args.users_file.is_empty() {
+        vec![]
+    } else {
+        let contents = fs::read_to_string(&args.users_file)
+            .map_err(|error| AppError::UsersFile(format!("{}: {}", args.users_file, error)))?;
+        parse_users_file(&contents)
+    };
+
+    match read_configs_from_files(&args.config_paths, &args.config_format) {
+        Ok(file_config) => {
+            let sources = resolve_sources(&file_config);
+            let labels = match args.group_by {
+                GroupBy::Label => file_config.labels,
+                GroupBy::Team if file_config.teams.is_empty() => {
+                    return Err(AppError::Usage(NO_TEAMS_ERROR.to_string()))
+                }
+                GroupBy::Team => team_labels(&file_config.teams),
+            };
+            Ok(AppParams {
+                labels,
+                header: file_config.header,
+                exclude: file_config.exclude,
+                users: merge_users(resolve_users(file_config.users, args.users), file_users),
+                exclude_closed_not_merged: file_config.exclude_closed_not_merged,
+                thanks_footer: file_config.thanks_footer,
+                output_style: file_config.output_style.unwrap_or_default(),
+                date: args.date,
+                date_sign: args.date_sign,
+                config_paths: args.config_paths,
+                strict: args.strict,
+                exclude_drafts: args.exclude_drafts || file_config.skip_drafts,
+                skip_bots: file_config.skip_bots,
+                exclude_users: merge_users(file_config.exclude_users, args.exclude_users),
+                auto_labels: file_config.auto_labels.unwrap_or_default(),
+                pending: file_config.pending,
+                quiet: args.quiet,
+                verbose: args.verbose,
+                toc: args.toc,
+                since_last_run: args.since_last_run,
+                include_reviews: args.include_reviews,
+                output: args.output,
+                highlights: file_config.highlights,
+                sort: args.sort,
+                order: args.order,
+                include_coauthors: args.include_coauthors,
+                no_definitions: args.no_definitions,
+                continue_on_error: args.continue_on_error,
+                org: args.org,
+                out_dir: args.out_dir,
+                mark_state: args.mark_state,
+                windows: args.windows,
+                since_watermarks: args.since_watermarks,
+                title: file_config.title,
+                rename: file_config.rename,
+                concurrency: args.concurrency.unwrap_or(DEFAULT_CONCURRENCY),
+                aliases: file_config.aliases,
+                user_aliases: file_config.user_aliases,
+                list_unknown_repos: args.list_unknown_repos,
+                update_config: args.update_config,
+                min_changes: args.min_changes,
+                exec: args.exec,
+                group_unknown: args.group_unknown,
+                exclude_title_patterns: file_config.exclude_title_patterns,
+                leaderboard: args.leaderboard,
+                split_by_label: args.split_by_label,
+                user_agent: args.user_agent,
+                validate: args.validate,
+                tracked_repos: file_config.tracked_repos,
+                include_issues: args.include_issues || file_config.include_issues,
+                reviews_heading: file_config.reviews_heading,
+                only_merged: args.only_merged || file_config.only_merged,
+                week: args.week,
+                output_pattern: file_config.output_pattern,
+                stdout: args.stdout,
+                format: args.format,
+                feed: args.feed,
+                cache: args.cache,
+                offline: args.offline,
+                publish: args.publish,
+                slack: file_config.slack.unwrap_or_default(),
+                discord: file_config.discord.unwrap_or_default(),
+                mastodon: file_config.mastodon.unwrap_or_default(),
+                github: file_config.github.unwrap_or_default(),
+                gitlab: file_config.gitlab.or_else(|| {
+                    sources
+                        .contains(&SourceKind::GitLab)
+                        .then(GitLabConfig::default)
+                }),
+                gitea: file_config.gitea.or_else(|| {
+                    sources
+                        .contains(&SourceKind::Gitea)
+                        .then(GiteaConfig::default)
+                }),
+                sources,
+                templates: file_config.templates.unwrap_or_default(),
+                front_matter: file_config.front_matter,
+                proxy: file_config.proxy,
+                members_of: file_config.org,
+                unknown_keys: file_config.unknown_keys,
+                schema: args.schema,
+            })
+        }
         Err(error) => {
-            println!("");
-            if args.config_path.len() == 0 {
-                println!("--config-path is not provided.");
-                println!("This will result with unlabelled items.");
+            if args.config_paths.len() == 0 {
+                if !args.quiet {
+                    eprintln!();
+                    eprintln!("--config-path is not provided.");
+                    eprintln!("This will result with unlabelled items.");
+                }
+            } else if is_not_found(error.as_ref()) {
+                return Err(AppError::ConfigNotFound(error.to_string()));
             } else {
-                println!("There was a problem reading your config file.");
-                println!("Check if your config file is correct and valid.");
-                println!("");
-                println!("{:?}", error);
+                return Err(AppError::ConfigParse(error.to_string()));
             }
 
-            AppParams {
+            if args.group_by == GroupBy::Team {
+                return Err(AppError::Usage(NO_TEAMS_ERROR.to_string()));
+            }
+
+            Ok(AppParams {
                 labels: vec![],
                 header: vec![],
                 exclude: vec![],
                 exclude_closed_not_merged: false,
-                users: args.users,
+                thanks_footer: false,
+                output_style: OutputStyle::default(),
+                highlights: vec![],
+                title: None,
+                rename: HashMap::new(),
+                aliases: HashMap::new(),
+                user_aliases: HashMap::new(),
+                exclude_title_patterns: vec![],
+                tracked_repos: vec![],
+                reviews_heading: None,
+                output_pattern: None,
+                templates: Templates::default(),
+                front_matter: None,
+                proxy: None,
+                members_of: None,
+                unknown_keys: vec![],
+                users: merge_users(args.users, file_users),
                 date: args.date,
                 date_sign: args.date_sign,
-                config_path: args.config_path,
-            }
+                config_paths: args.config_paths,
+                strict: args.strict,
+                exclude_drafts: args.exclude_drafts,
+                skip_bots: false,
+                exclude_users: merge_users(vec![], args.exclude_users),
+                auto_labels: AutoLabels::default(),
+                pending: vec![],
+                quiet: args.quiet,
+                verbose: args.verbose,
+                toc: args.toc,
+                since_last_run: args.since_last_run,
+                include_reviews: args.include_reviews,
+                output: args.output,
+                sort: args.sort,
+                order: args.order,
+                include_coauthors: args.include_coauthors,
+                no_definitions: args.no_definitions,
+                continue_on_error: args.continue_on_error,
+                org: args.org,
+                out_dir: args.out_dir,
+                mark_state: args.mark_state,
+                windows: args.windows,
+                since_watermarks: args.since_watermarks,
+                concurrency: args.concurrency.unwrap_or(DEFAULT_CONCURRENCY),
+                list_unknown_repos: args.list_unknown_repos,
+                update_config: args.update_config,
+                min_changes: args.min_changes,
+                exec: args.exec,
+                group_unknown: args.group_unknown,
+                leaderboard: args.leaderboard,
+                split_by_label: args.split_by_label,
+                user_agent: args.user_agent,
+                validate: args.validate,
+                include_issues: args.include_issues,
+                only_merged: args.only_merged,
+                week: args.week,
+                stdout: args.stdout,
+                format: args.format,
+                feed: args.feed,
+                cache: args.cache,
+                offline: args.offline,
+                publish: args.publish,
+                slack: SlackConfig::default(),
+                discord: DiscordConfig::default(),
+                mastodon: MastodonConfig::default(),
+                github: GitHubConfig::default(),
+                gitlab: None,
+                gitea: None,
+                sources: vec![SourceKind::GitHub],
+                schema: args.schema,
+            })
         }
     }
 }
 
-fn read_args() -> Vec<Arg> {
-    let mut args = vec![];
-    for pair in env::args().skip(1) {
-        let split = pair.split("=").collect::<Vec<&str>>();
+pub fn write_completions<W: Write>(shell: Shell, writer: &mut W) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
 
-        let name = split[0].to_string();
-        let value = if split.len() == 1 {
-            String::from("")
-        } else {
-            split[1].to_string()
-        };
+    clap_complete::generate(shell, &mut command, name, writer);
+}
 
-        args.push(Arg(name, value));
+fn parse_concurrency(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(concurrency) if concurrency > 0 => Ok(concurrency),
+        _ => Err(String::from("expected a number greater than 0")),
     }
-
-    args
 }
 
-fn process_args(pairs: Vec<Arg>) -> Args {
-    let mut args = Args {
-        users: vec![],
-        date: String::from(""),
-        date_sign: String::from(""),
-        config_path: String::from(""),
+/// `-before` and `-after` are still accepted for `--before` and `--after`.
+fn parse_cli_args<I: IntoIterator<Item = String>>(argv: I) -> Result<Args, clap::Error> {
+    let argv = argv.into_iter().map(|arg| match arg.as_str() {
+        "-before" | "-after" => format!("-{}", arg),
+        _ => arg,
+    });
+
+    let cli = Cli::try_parse_from(argv)?;
+    let mut args = match cli.command {
+        None => cli.args,
+        Some(Command::Generate(args)) => args,
+        Some(Command::Validate { schema, mut args }) => {
+            args.validate = true;
+            args.schema = schema;
+            args
+        }
+        Some(Command::Init(mut args)) => {
+            args.init = true;
+            args
+        }
+        Some(Command::Publish { target, mut args }) => {
+            args.publish = Some(target);
+            args
+        }
+        Some(Command::Completions { shell }) => Args {
+            completions: Some(shell),
+            ..Args::default()
+        },
     };
 
-    for pair in pairs {
-        match (pair.0.as_str(), pair.1.as_str()) {
-            ("--users", value) => {
-                args.users.append(
-                    &mut value
-                        .split(",")
-                        .map(|user| user.to_string())
-                        .collect::<Vec<String>>(),
-                );
+    if args.before {
+        args.date_sign = String::from("<");
+    }
+    if args.after {
+        args.date_sign = String::from(">");
+    }
+
+    if let Some(date) = relative_date(&args.date, today(args.timezone)) {
+        if date.contains("..") {
+            args.date_sign = String::from("");
+        }
+        args.date = date;
+    }
+
+    if !args.week.is_empty() {
+        match iso_week(&args.week, today(args.timezone)) {
+            Some((week, monday, sunday)) => {
+                args.week = week;
+                args.from = monday.format("%Y-%m-%d").to_string();
+                args.until = sunday.format("%Y-%m-%d").to_string();
             }
-            ("--date", value) => {
-                args.date = value.to_string();
+            None => {
+                return Err(Cli::command().error(
+                    ErrorKind::InvalidValue,
+                    format!("invalid value '{}' for '--week <WEEK>'", args.week),
+                ))
             }
-            ("-before", _) => args.date_sign = String::from("<"),
-            ("-after", _) => args.date_sign = String::from(">"),
-            ("--config-path", value) => args.config_path = value.to_string(),
-            (name, value) => println!("Could not handle argument {} with value {}", name, value),
         }
     }
 
-    args
+    if let Some((date, date_sign)) = bounded_date(&args.from, &args.until) {
+        args.date = date;
+        args.date_sign = date_sign;
+    }
+
+    Ok(args)
 }
 
-fn read_config_from_file<P: AsRef<Path>>(path: P) -> Result<FileConfig, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+fn today(timezone: Option<FixedOffset>) -> NaiveDate {
+    match timezone {
+        Some(timezone) => Utc::now().with_timezone(&timezone).date_naive(),
+        None => Local::now().date_naive(),
+    }
+}
 
-    let config = serde_json::from_reader(reader)?;
+/// Resolves `today`, `yesterday`, `last week` and `past N days` to a date or a date range.
+fn relative_date(date: &str, today: NaiveDate) -> Option<String> {
+    let format = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
 
-    Ok(config)
+    match date.trim().to_lowercase().as_str() {
+        "today" => Some(format(today)),
+        "yesterday" => Some(format(today - Duration::days(1))),
+        "last week" => {
+            let (_, monday, sunday) = iso_week("last", today)?;
+            Some(format!("{}..{}", format(monday), format(sunday)))
+        }
+        phrase => {
+            let days = phrase.strip_prefix("past ")?;
+            let days = days
+                .strip_suffix(" days")
+                .or_else(|| days.strip_suffix(" day"))?
+                .parse::<i64>()
+                .ok()
+                .filter(|days| *days > 0)?;
+            Some(format!(
+                "{}..{}",
+                format(today - Duration::days(days - 1)),
+                format(today)
+            ))
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Resolves `YYYY-Www` or `last`, the week before `today`, to its name, Monday and Sunday.
+fn iso_week(week: &str, today: NaiveDate) -> Option<(String, NaiveDate, NaiveDate)> {
+    let (year, number) = if week == "last" {
+        let iso_week = (today - Duration::days(7)).iso_week();
+        (iso_week.year(), iso_week.week())
+    } else {
+        let (year, number) = week.split_once("-W")?;
+        (year.parse::<i32>().ok()?, number.parse::<u32>().ok()?)
+    };
 
-    #[test]
-    fn it_processes_args() {
-        let expected = Args {
-            users: vec![],
-            date: "".to_string(),
-            date_sign: "".to_string(),
-            config_path: "".to_string(),
-        };
+    let monday = NaiveDate::from_isoywd_opt(year, number, Weekday::Mon)?;
+    Some((
+        format!("{}-W{:02}", year, number),
+        monday,
+        monday + Duration::days(6),
+    ))
+}
 
-        assert_eq!(expected, process_args(vec![]));
+/// `--from` and `--until` are inclusive, they override `--date` and `-before`/`-after`.
+fn bounded_date(from: &str, until: &str) -> Option<(String, String)> {
+    match (from.is_empty(), until.is_empty()) {
+        (false, false) => Some((format!("{}..{}", from, until), String::from(""))),
+        (false, true) => Some((from.to_string(), String::from(">="))),
+        (true, false) => Some((until.to_string(), String::from("<="))),
+        (true, true) => None,
     }
+}
 
-    #[test]
-    fn it_processes_users_args() {
-        let expected = Args {
-            users: vec!["BobrImperator".to_string()],
-            date: "".to_string(),
-            date_sign: "".to_string(),
-            config_path: "".to_string(),
-        };
+/// Without `sources` GitHub is queried, together with the forges that have a config.
+fn resolve_sources(file_config: &FileConfig) -> Vec<SourceKind> {
+    if let Some(sources) = &file_config.sources {
+        return sources.clone();
+    }
 
-        assert_eq!(
-            expected,
-            process_args(vec![Arg(
-                "--users".to_string(),
-                "BobrImperator".to_string()
-            )])
-        );
+    let mut sources = vec![SourceKind::GitHub];
+    if file_config.gitlab.is_some() {
+        sources.push(SourceKind::GitLab);
+    }
+    if file_config.gitea.is_some() {
+        sources.push(SourceKind::Gitea);
     }
 
-    #[test]
-    fn it_processes_multiple_users_args() {
-        let expected = Args {
-            users: vec!["BobrImperator".to_string(), "mansona".to_string()],
-            date: "".to_string(),
-            date_sign: "".to_string(),
-            config_path: "".to_string(),
-        };
+    sources
+}
 
-        assert_eq!(
-            expected,
-            process_args(vec![Arg(
-                "--users".to_string(),
-                "BobrImperator,mansona".to_string()
-            )])
-        );
+fn resolve_users(config_users: Vec<String>, cli_users: Vec<String>) -> Vec<String> {
+    if config_users.is_empty() {
+        cli_users
+    } else {
+        config_users
     }
+}
 
-    #[test]
-    fn it_processes_date_args() {
-        let expected = Args {
+/// Reads one login per line, skipping blank lines and `#` comments.
+fn parse_users_file(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Labels matching the PRs of the members of each team on any repository, in the order of their
+/// names.
+fn team_labels(teams: &BTreeMap<String, Vec<String>>) -> Vec<LabelConfig> {
+    teams
+        .iter()
+        .map(|(name, members)| LabelConfig {
+            name: name.clone(),
+            repos: vec![],
             users: vec![],
-            date: "2022-02-18".to_string(),
-            date_sign: "".to_string(),
-            config_path: "".to_string(),
-        };
+            rules: vec![LabelRule {
+                users: members.clone(),
+                ..LabelRule::default()
+            }],
+            templates: Templates::default(),
+        })
+        .collect()
+}
 
-        assert_eq!(
-            expected,
-            process_args(vec![Arg("--date".to_string(), "2022-02-18".to_string())])
-        );
+/// Logins are case-insensitive on GitHub, so only the first spelling of each one is kept.
+pub fn merge_users(users: Vec<String>, extra_users: Vec<String>) -> Vec<String> {
+    let mut merged: Vec<String> = vec![];
+
+    for user in users.into_iter().chain(extra_users) {
+        if !merged
+            .iter()
+            .any(|merged_user| merged_user.eq_ignore_ascii_case(&user))
+        {
+            merged.push(user);
+        }
     }
 
-    #[test]
-    fn it_processes_after_args() {
-        let expected = Args {
-            users: vec![],
-            date: "".to_string(),
-            date_sign: ">".to_string(),
-            config_path: "".to_string(),
-        };
+    merged
+}
 
-        assert_eq!(
-            expected,
-            process_args(vec![Arg("-after".to_string(), "".to_string())])
-        );
+fn is_repository_name(name: &str) -> bool {
+    match name.split_once('/') {
+        Some((organization, repository)) => {
+            !organization.is_empty()
+                && !repository.is_empty()
+                && !repository.contains('/')
+                && !name.contains(char::is_whitespace)
+        }
+        None => false,
     }
+}
 
-    #[test]
-    fn it_processes_before_args() {
-        let expected = Args {
-            users: vec![],
-            date: "".to_string(),
-            date_sign: "<".to_string(),
-            config_path: "".to_string(),
-        };
+fn find_duplicate_label_names(labels: &[LabelConfig]) -> Vec<String> {
+    let mut names: Vec<&String> = vec![];
+    let mut duplicate_names: Vec<String> = vec![];
 
-        assert_eq!(
-            expected,
-            process_args(vec![Arg("-before".to_string(), "".to_string())])
-        );
+    for label in labels {
+        if names.contains(&&label.name) && !duplicate_names.contains(&label.name) {
+            duplicate_names.push(label.name.clone());
+        }
+        names.push(&label.name);
     }
 
-    #[test]
-    fn it_processes_config_path_args() {
-        let expected = Args {
-            users: vec![],
-            date: "".to_string(),
-            date_sign: "".to_string(),
-            config_path: "../config/location.json".to_string(),
-        };
+    duplicate_names
+}
 
-        assert_eq!(
+/// Lists every problem of the config, without making any requests.
+pub fn validate_config(app_params: &AppParams) -> Vec<String> {
+    let mut problems: Vec<String> = vec![];
+
+    // Labels with the same name are merged across files, so only duplicates within a file are reported.
+    // stdin can't be read a second time.
+    for path in app_params.config_paths.iter().filter(|path| *path != "-") {
+        if let Ok(config) = read_config_from_file(path) {
+            for name in find_duplicate_label_names(&config.labels) {
+                problems.push(format!("{} lists the label {} more than once.", path, name));
+            }
+        }
+    }
+
+    for (repo, label_names) in find_duplicate_repos(&app_params.labels) {
+        problems.push(format!(
+            "{} is listed under multiple labels: {}.",
+            repo,
+            label_names.join(", ")
+        ));
+    }
+
+    for label in &app_params.labels {
+        if label.repos.is_empty() && label.rules.is_empty() {
+            problems.push(format!("The label {} has no repos.", label.name));
+        }
+        for repo in &label.repos {
+            match repo.strip_prefix("regex:") {
+                Some(pattern) => {
+                    if let Err(error) = regex::Regex::new(pattern) {
+                        problems.push(format!(
+                            "The label {} lists an invalid pattern {:?}: {}",
+                            label.name, repo, error
+                        ));
+                    }
+                }
+                None if !is_repository_name(repo) => problems.push(format!(
+                    "The label {} lists a malformed repo {:?}.",
+                    label.name, repo
+                )),
+                None => {}
+            }
+        }
+    }
+
+    for repo in app_params
+        .exclude
+        .iter()
+        .filter(|repo| !is_repository_name(repo))
+    {
+        problems.push(format!("exclude lists a malformed repo {:?}.", repo));
+    }
+
+    for key in &app_params.unknown_keys {
+        problems.push(format!("The config has an unknown key {}.", key));
+    }
+
+    let label_templates = app_params
+        .labels
+        .iter()
+        .map(|label| (format!("The label {}", label.name), &label.templates));
+    for (owner, templates) in
+        std::iter::once((String::from("templates"), &app_params.templates)).chain(label_templates)
+    {
+        for template in templates.item.iter().chain(templates.label.iter()) {
+            if let Err(error) = handlebars::Template::compile(template) {
+                problems.push(format!("{} has an invalid template: {}", owner, error));
+            }
+        }
+    }
+
+    for repo in app_params
+        .tracked_repos
+        .iter()
+        .filter(|repo| !is_repository_name(repo))
+    {
+        problems.push(format!("tracked_repos lists a malformed repo {:?}.", repo));
+    }
+
+    let mut rename = app_params
+        .rename
+        .iter()
+        .collect::<Vec<(&String, &String)>>();
+    rename.sort();
+    for (repo, display_name) in rename {
+        if !is_repository_name(repo) {
+            problems.push(format!("rename lists a malformed repo {:?}.", repo));
+        }
+        if display_name.trim().is_empty() {
+            problems.push(format!("rename has an empty name for {}.", repo));
+        }
+    }
+
+    problems
+}
+
+pub fn find_duplicate_repos(labels: &[LabelConfig]) -> Vec<(String, Vec<String>)> {
+    let mut duplicate_repos: Vec<(String, Vec<String>)> = vec![];
+
+    for label in labels {
+        for repo in &label.repos {
+            match duplicate_repos
+                .iter_mut()
+                .find(|(name, _)| name.eq_ignore_ascii_case(repo))
+            {
+                Some((_, label_names)) => {
+                    if !label_names.contains(&label.name) {
+                        label_names.push(label.name.clone());
+                    }
+                }
+                None => duplicate_repos.push((repo.clone(), vec![label.name.clone()])),
+            }
+        }
+    }
+
+    duplicate_repos
+        .into_iter()
+        .filter(|(_, label_names)| label_names.len() > 1)
+        .collect::<Vec<_>>()
+}
+
+fn read_configs_from_files(
+    paths: &[String],
+    format: &ConfigFormat,
+) -> Result<FileConfig, Box<dyn Error>> {
+    if paths.is_empty() {
+        return Err("--config-path is not provided".into());
+    }
+
+    let configs = paths
+        .iter()
+        .map(|path| read_config(path, format))
+        .collect::<Result<Vec<FileConfig>, Box<dyn Error>>>()?;
+
+    Ok(merge_configs(configs))
+}
+
+fn merge_configs(configs: Vec<FileConfig>) -> FileConfig {
+    let mut merged = FileConfig::default();
+
+    for config in configs {
+        for label in config.labels {
+            match merged
+                .labels
+                .iter_mut()
+                .find(|merged_label| merged_label.name == label.name)
+            {
+                Some(merged_label) => {
+                    for repo in label.repos {
+                        if !merged_label.repos.contains(&repo) {
+                            merged_label.repos.push(repo);
+                        }
+                    }
+                    for user in label.users {
+                        if !merged_label.users.contains(&user) {
+                            merged_label.users.push(user);
+                        }
+                    }
+                    merged_label.rules.extend(label.rules);
+                }
+                None => merged.labels.push(label),
+            }
+        }
+
+        merged.header.extend(config.header);
+
+        for user in config.users {
+            if !merged.users.contains(&user) {
+                merged.users.push(user);
+            }
+        }
+
+        for repo in config.exclude {
+            if !merged.exclude.contains(&repo) {
+                merged.exclude.push(repo);
+            }
+        }
+
+        merged.exclude_closed_not_merged |= config.exclude_closed_not_merged;
+        merged.thanks_footer |= config.thanks_footer;
+        if config.output_style.is_some() {
+            merged.output_style = config.output_style;
+        }
+        for highlight in config.highlights {
+            if !merged.highlights.contains(&highlight) {
+                merged.highlights.push(highlight);
+            }
+        }
+
+        if config.title.is_some() {
+            merged.title = config.title;
+        }
+
+        merged.rename.extend(config.rename);
+
+        merged.aliases.extend(config.aliases);
+        merged.user_aliases.extend(config.user_aliases);
+
+        for pattern in config.exclude_title_patterns {
+            if !merged.exclude_title_patterns.contains(&pattern) {
+                merged.exclude_title_patterns.push(pattern);
+            }
+        }
+
+        for repo in config.tracked_repos {
+            if !merged.tracked_repos.contains(&repo) {
+                merged.tracked_repos.push(repo);
+            }
+        }
+
+        merged.include_issues |= config.include_issues;
+
+        if config.reviews_heading.is_some() {
+            merged.reviews_heading = config.reviews_heading;
+        }
+
+        merged.only_merged |= config.only_merged;
+
+        merged.skip_drafts |= config.skip_drafts;
+
+        merged.skip_bots |= config.skip_bots;
+
+        for user in config.exclude_users {
+            if !merged.exclude_users.contains(&user) {
+                merged.exclude_users.push(user);
+            }
+        }
+
+        for repo in config.pending {
+            if !merged.pending.contains(&repo) {
+                merged.pending.push(repo);
+            }
+        }
+
+        if let Some(auto_labels) = config.auto_labels {
+            let merged_auto_labels = merged.auto_labels.get_or_insert_with(AutoLabels::default);
+            merged_auto_labels.topics.extend(auto_labels.topics);
+            merged_auto_labels.languages.extend(auto_labels.languages);
+        }
+
+        if config.output_pattern.is_some() {
+            merged.output_pattern = config.output_pattern;
+        }
+
+        if config.templates.is_some() {
+            merged.templates = config.templates;
+        }
+
+        if config.front_matter.is_some() {
+            merged.front_matter = config.front_matter;
+        }
+
+        if config.slack.is_some() {
+            merged.slack = config.slack;
+        }
+
+        if config.discord.is_some() {
+            merged.discord = config.discord;
+        }
+
+        if config.mastodon.is_some() {
+            merged.mastodon = config.mastodon;
+        }
+
+        if config.github.is_some() {
+            merged.github = config.github;
+        }
+
+        if config.gitlab.is_some() {
+            merged.gitlab = config.gitlab;
+        }
+
+        if config.gitea.is_some() {
+            merged.gitea = config.gitea;
+        }
+
+        if config.sources.is_some() {
+            merged.sources = config.sources;
+        }
+
+        if config.proxy.is_some() {
+            merged.proxy = config.proxy;
+        }
+
+        if config.org.is_some() {
+            merged.org = config.org;
+        }
+
+        merged.teams.extend(config.teams);
+
+        merged.unknown_keys.extend(config.unknown_keys);
+    }
+
+    merged
+}
+
+fn is_not_found(error: &(dyn Error + 'static)) -> bool {
+    match error.downcast_ref::<io::Error>() {
+        Some(error) => error.kind() == io::ErrorKind::NotFound,
+        None => false,
+    }
+}
+
+/// `-` reads the config from stdin, e.g. when it's generated by another command.
+fn read_config(path: &str, format: &ConfigFormat) -> Result<FileConfig, Box<dyn Error>> {
+    if path == "-" {
+        read_config_in_format(io::stdin().lock(), format)
+    } else {
+        read_config_from_file(path)
+    }
+}
+
+fn read_config_in_format<R: Read>(
+    mut reader: R,
+    format: &ConfigFormat,
+) -> Result<FileConfig, Box<dyn Error>> {
+    let mut contents = String::new();
+    match format {
+        ConfigFormat::Json => read_config_from_reader(reader),
+        ConfigFormat::Toml => {
+            reader.read_to_string(&mut contents)?;
+            read_config_from_toml(&contents)
+        }
+        ConfigFormat::Yaml => {
+            reader.read_to_string(&mut contents)?;
+            read_config_from_yaml(&contents)
+        }
+    }
+}
+
+/// `.toml` files are read as TOML, `.yaml` and `.yml` files as YAML, any other file as JSON.
+fn read_config_from_file<P: AsRef<Path>>(path: P) -> Result<FileConfig, Box<dyn Error>> {
+    match path
+        .as_ref()
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("toml") => return read_config_from_toml(&fs::read_to_string(path)?),
+        Some("yaml") | Some("yml") => return read_config_from_yaml(&fs::read_to_string(path)?),
+        _ => {}
+    }
+
+    let file = File::open(path)?;
+
+    read_config_from_reader(BufReader::new(file))
+}
+
+fn read_config_from_toml(contents: &str) -> Result<FileConfig, Box<dyn Error>> {
+    read_config_from_value(toml::from_str(contents)?)
+}
+
+fn read_config_from_yaml(contents: &str) -> Result<FileConfig, Box<dyn Error>> {
+    read_config_from_value(serde_yaml::from_str(contents)?)
+}
+
+/// Serde ignores unknown keys, so they are collected beforehand to be reported.
+fn read_config_from_value(value: serde_json::Value) -> Result<FileConfig, Box<dyn Error>> {
+    let mut unknown_keys = vec![];
+    find_unknown_keys(&value, &config_schema(), "", &mut unknown_keys);
+
+    let mut config: FileConfig = serde_json::from_value(value)?;
+    config.unknown_keys = unknown_keys;
+
+    Ok(config)
+}
+
+fn read_config_from_reader<R: Read>(reader: R) -> Result<FileConfig, Box<dyn Error>> {
+    read_config_from_value(serde_json::from_reader(reader)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(argv: &[&str]) -> Args {
+        parse_cli_args(
+            std::iter::once("this-week-in-open-source")
+                .chain(argv.iter().copied())
+                .map(String::from),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_processes_args() {
+        let expected = Args {
+            users: vec![],
+            date: "".to_string(),
+            date_sign: "".to_string(),
+            before: false,
+            after: false,
+            config_paths: vec![],
+            config_format: ConfigFormat::Json,
+            strict: false,
+            exclude_drafts: false,
+            quiet: false,
+            verbose: false,
+            toc: false,
+            since_last_run: false,
+            include_reviews: false,
+            output: "".to_string(),
+            sort: SortField::Repository,
+            order: SortOrder::Asc,
+            include_coauthors: false,
+            no_definitions: false,
+            continue_on_error: false,
+            org: "".to_string(),
+            out_dir: "".to_string(),
+            mark_state: false,
+            windows: vec![],
+            since_watermarks: false,
+            concurrency: None,
+            list_unknown_repos: false,
+            update_config: None,
+            min_changes: None,
+            exec: "".to_string(),
+            group_unknown: false,
+            users_file: "".to_string(),
+            leaderboard: false,
+            split_by_label: false,
+            user_agent: "".to_string(),
+            validate: false,
+            include_issues: false,
+            only_merged: false,
+            from: "".to_string(),
+            until: "".to_string(),
+            week: "".to_string(),
+            timezone: None,
+            stdout: false,
+            format: OutputFormat::Markdown,
+            feed: "".to_string(),
+            cache: false,
+            offline: false,
+            group_by: GroupBy::Label,
+            exclude_users: vec![],
+            schema: false,
+            init: false,
+            completions: None,
+            publish: None,
+        };
+
+        assert_eq!(expected, parse_args(&[]));
+    }
+
+    #[test]
+    fn it_processes_users_args() {
+        let expected = Args {
+            users: vec!["BobrImperator".to_string()],
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--users=BobrImperator"]));
+    }
+
+    #[test]
+    fn it_processes_multiple_users_args() {
+        let expected = Args {
+            users: vec!["BobrImperator".to_string(), "mansona".to_string()],
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--users=BobrImperator,mansona"]));
+    }
+
+    #[test]
+    fn it_processes_date_args() {
+        let expected = Args {
+            date: "2022-02-18".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--date=2022-02-18"]));
+    }
+
+    #[test]
+    fn it_processes_after_args() {
+        let expected = Args {
+            date_sign: ">".to_string(),
+            after: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["-after"]));
+    }
+
+    #[test]
+    fn it_processes_before_args() {
+        let expected = Args {
+            date_sign: "<".to_string(),
+            before: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["-before"]));
+    }
+
+    #[test]
+    fn it_processes_config_path_args() {
+        let expected = Args {
+            config_paths: vec!["../config/location.json".to_string()],
+            ..Args::default()
+        };
+
+        assert_eq!(
             expected,
-            process_args(vec![Arg(
-                "--config-path".to_string(),
-                "../config/location.json".to_string()
-            )])
+            parse_args(&["--config-path=../config/location.json"])
         );
     }
 
@@ -270,15 +1629,1196 @@ mod tests {
         assert_eq!(
             AppParams {
                 exclude_closed_not_merged: false,
+                thanks_footer: false,
+                output_style: OutputStyle::default(),
                 labels: vec![],
                 header: vec![],
                 users: vec![],
                 exclude: vec![],
-                config_path: "".to_string(),
+                config_paths: vec![],
+                strict: false,
                 date: "".to_string(),
-                date_sign: "".to_string()
+                date_sign: "".to_string(),
+                exclude_drafts: false,
+                skip_bots: false,
+                exclude_users: vec![],
+                auto_labels: AutoLabels::default(),
+                quiet: false,
+                verbose: false,
+                toc: false,
+                since_last_run: false,
+                include_reviews: false,
+                output: "".to_string(),
+                highlights: vec![],
+                sort: SortField::Repository,
+                order: SortOrder::Asc,
+                include_coauthors: false,
+                no_definitions: false,
+                continue_on_error: false,
+                org: "".to_string(),
+                out_dir: "".to_string(),
+                mark_state: false,
+                windows: vec![],
+                since_watermarks: false,
+                title: None,
+                rename: HashMap::new(),
+                concurrency: DEFAULT_CONCURRENCY,
+                aliases: HashMap::new(),
+                user_aliases: HashMap::new(),
+                list_unknown_repos: false,
+                update_config: None,
+                pending: vec![],
+                min_changes: None,
+                exec: "".to_string(),
+                group_unknown: false,
+                exclude_title_patterns: vec![],
+                leaderboard: false,
+                split_by_label: false,
+                user_agent: "".to_string(),
+                validate: false,
+                tracked_repos: vec![],
+                include_issues: false,
+                reviews_heading: None,
+                only_merged: false,
+                week: "".to_string(),
+                output_pattern: None,
+                stdout: false,
+                format: OutputFormat::Markdown,
+                feed: "".to_string(),
+                cache: false,
+                offline: false,
+                publish: None,
+                slack: SlackConfig::default(),
+                discord: DiscordConfig::default(),
+                mastodon: MastodonConfig::default(),
+                github: GitHubConfig::default(),
+                gitlab: None,
+                gitea: None,
+                sources: vec![SourceKind::GitHub],
+                templates: Templates::default(),
+                front_matter: None,
+                proxy: None,
+                members_of: None,
+                unknown_keys: vec![],
+                schema: false,
+            },
+            app_params(Args::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_processes_multiple_config_path_args() {
+        let expected = Args {
+            config_paths: vec![
+                "rust.json".to_string(),
+                "ember.json".to_string(),
+                "team.json".to_string(),
+            ],
+            ..Args::default()
+        };
+
+        assert_eq!(
+            expected,
+            parse_args(&[
+                "--config-path=rust.json,ember.json",
+                "--config-path=team.json"
+            ])
+        );
+    }
+
+    #[test]
+    fn it_merges_configs() {
+        let rust_config = FileConfig {
+            labels: vec![LabelConfig {
+                name: "Rust".to_string(),
+                repos: vec!["rust-lang/crates.io".to_string()],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            }],
+            header: vec!["Rust header".to_string()],
+            users: vec!["Turbo87".to_string()],
+            exclude: vec!["Turbo87/aprs-parser-rs".to_string()],
+            exclude_closed_not_merged: false,
+            thanks_footer: false,
+            output_style: None,
+            ..FileConfig::default()
+        };
+        let ember_config = FileConfig {
+            labels: vec![LabelConfig {
+                name: "Ember".to_string(),
+                repos: vec!["ember-cli/ember-exam".to_string()],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            }],
+            header: vec!["Ember header".to_string()],
+            users: vec!["Turbo87".to_string(), "BobrImperator".to_string()],
+            exclude: vec!["Turbo87/aprs-parser-rs".to_string()],
+            exclude_closed_not_merged: true,
+            thanks_footer: true,
+            output_style: Some(OutputStyle {
+                definitions_separator: "\n".to_string(),
+                trailing_newline: false,
+            }),
+            title: Some("This Week in Open Source {date}".to_string()),
+            ..FileConfig::default()
+        };
+
+        assert_eq!(
+            FileConfig {
+                labels: vec![
+                    LabelConfig {
+                        name: "Rust".to_string(),
+                        repos: vec!["rust-lang/crates.io".to_string()],
+                        users: vec![],
+                        rules: vec![],
+                        templates: Templates::default(),
+                    },
+                    LabelConfig {
+                        name: "Ember".to_string(),
+                        repos: vec!["ember-cli/ember-exam".to_string()],
+                        users: vec![],
+                        rules: vec![],
+                        templates: Templates::default(),
+                    }
+                ],
+                header: vec!["Rust header".to_string(), "Ember header".to_string()],
+                users: vec!["Turbo87".to_string(), "BobrImperator".to_string()],
+                exclude: vec!["Turbo87/aprs-parser-rs".to_string()],
+                exclude_closed_not_merged: true,
+                thanks_footer: true,
+                output_style: Some(OutputStyle {
+                    definitions_separator: "\n".to_string(),
+                    trailing_newline: false,
+                }),
+                title: Some("This Week in Open Source {date}".to_string()),
+                ..FileConfig::default()
+            },
+            merge_configs(vec![rust_config, ember_config])
+        );
+    }
+
+    #[test]
+    fn it_merges_repos_of_labels_with_the_same_name() {
+        let first_config = FileConfig {
+            labels: vec![LabelConfig {
+                name: "Ember".to_string(),
+                repos: vec![
+                    "ember-cli/ember-exam".to_string(),
+                    "ember-engines/ember-engines".to_string(),
+                ],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            }],
+            ..FileConfig::default()
+        };
+        let second_config = FileConfig {
+            labels: vec![LabelConfig {
+                name: "Ember".to_string(),
+                repos: vec![
+                    "ember-engines/ember-engines".to_string(),
+                    "emberjs/ember.js".to_string(),
+                ],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            }],
+            ..FileConfig::default()
+        };
+
+        assert_eq!(
+            vec![LabelConfig {
+                name: "Ember".to_string(),
+                repos: vec![
+                    "ember-cli/ember-exam".to_string(),
+                    "ember-engines/ember-engines".to_string(),
+                    "emberjs/ember.js".to_string(),
+                ],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            }],
+            merge_configs(vec![first_config, second_config]).labels
+        );
+    }
+
+    #[test]
+    fn it_processes_strict_args() {
+        let expected = Args {
+            strict: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--strict"]));
+    }
+
+    #[test]
+    fn it_finds_repos_listed_in_multiple_labels() {
+        let labels = vec![
+            LabelConfig {
+                name: "Rust".to_string(),
+                repos: vec![
+                    "rust-lang/crates.io".to_string(),
+                    "Turbo87/aprs-parser-rs".to_string(),
+                ],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            },
+            LabelConfig {
+                name: "Ember".to_string(),
+                repos: vec!["ember-cli/ember-exam".to_string()],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
             },
-            args()
+            LabelConfig {
+                name: "Turbo87".to_string(),
+                repos: vec!["turbo87/aprs-parser-rs".to_string()],
+                users: vec![],
+                rules: vec![],
+                templates: Templates::default(),
+            },
+        ];
+
+        assert_eq!(
+            vec![(
+                "Turbo87/aprs-parser-rs".to_string(),
+                vec!["Rust".to_string(), "Turbo87".to_string()]
+            )],
+            find_duplicate_repos(&labels)
         );
     }
+
+    #[test]
+    fn it_processes_exclude_drafts_args() {
+        let expected = Args {
+            exclude_drafts: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--exclude-drafts"]));
+    }
+
+    #[test]
+    fn it_processes_quiet_args() {
+        let expected = Args {
+            quiet: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--quiet"]));
+        assert_eq!(expected, parse_args(&["-q"]));
+    }
+
+    #[test]
+    fn it_processes_verbose_args() {
+        let expected = Args {
+            verbose: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["-v"]));
+        assert!(parse_cli_args(["twios", "-v", "-q"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn it_tells_missing_config_files_apart_from_invalid_ones() {
+        let missing = read_config_from_file("does/not/exist.json").unwrap_err();
+        assert!(is_not_found(missing.as_ref()));
+
+        let invalid = read_config_from_file("Cargo.toml").unwrap_err();
+        assert!(!is_not_found(invalid.as_ref()));
+    }
+
+    #[test]
+    fn it_fails_on_a_missing_config_file_even_when_quiet() {
+        let args = Args {
+            config_paths: vec!["does/not/exist.json".to_string()],
+            quiet: true,
+            ..Args::default()
+        };
+
+        assert!(matches!(app_params(args), Err(AppError::ConfigNotFound(_))));
+    }
+
+    #[test]
+    fn it_processes_toc_args() {
+        let expected = Args {
+            toc: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--toc"]));
+    }
+
+    #[test]
+    fn it_processes_since_last_run_args() {
+        let expected = Args {
+            since_last_run: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--since-last-run"]));
+    }
+
+    #[test]
+    fn it_processes_include_reviews_args() {
+        let expected = Args {
+            include_reviews: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--include-reviews"]));
+    }
+
+    #[test]
+    fn it_processes_output_args() {
+        let expected = Args {
+            output: "content/posts/twios.md".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--output=content/posts/twios.md"]));
+    }
+
+    #[test]
+    fn it_processes_sort_args() {
+        let expected = Args {
+            sort: SortField::Created,
+            order: SortOrder::Desc,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--sort=created", "--order=desc"]));
+    }
+
+    #[test]
+    fn it_resolves_users_from_config() {
+        assert_eq!(
+            vec!["Turbo87".to_string()],
+            resolve_users(
+                vec!["Turbo87".to_string()],
+                vec!["BobrImperator".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn it_resolves_users_from_cli_without_config_users() {
+        assert_eq!(
+            vec!["BobrImperator".to_string()],
+            resolve_users(vec![], vec!["BobrImperator".to_string()])
+        );
+    }
+
+    #[test]
+    fn it_processes_include_coauthors_args() {
+        let expected = Args {
+            include_coauthors: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--include-coauthors"]));
+    }
+
+    #[test]
+    fn it_processes_no_definitions_args() {
+        let expected = Args {
+            no_definitions: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--no-definitions"]));
+    }
+
+    #[test]
+    fn it_processes_continue_on_error_args() {
+        let expected = Args {
+            continue_on_error: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--continue-on-error"]));
+    }
+
+    #[test]
+    fn it_processes_org_args() {
+        let expected = Args {
+            org: "simplabs".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--org=simplabs"]));
+    }
+
+    #[test]
+    fn it_processes_out_dir_args() {
+        let expected = Args {
+            out_dir: "content/posts".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--out-dir=content/posts"]));
+    }
+
+    #[test]
+    fn it_processes_mark_state_args() {
+        let expected = Args {
+            mark_state: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--mark-state"]));
+    }
+
+    #[test]
+    fn it_processes_windows_arg() {
+        let expected = Args {
+            windows: vec![
+                "2024-01-01..2024-01-07".to_string(),
+                "2024-01-08..2024-01-14".to_string(),
+            ],
+            ..Args::default()
+        };
+
+        assert_eq!(
+            expected,
+            parse_args(&["--windows=2024-01-01..2024-01-07,2024-01-08..2024-01-14"])
+        );
+    }
+
+    #[test]
+    fn it_processes_concurrency_arg() {
+        let expected = Args {
+            concurrency: Some(5),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--concurrency=5"]));
+        assert!(parse_cli_args(["twios", "--concurrency=0"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn it_processes_min_changes_arg() {
+        let expected = Args {
+            min_changes: Some(10),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--min-changes=10"]));
+    }
+
+    #[test]
+    fn it_processes_users_file_args() {
+        let expected = Args {
+            users_file: "users.txt".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--users-file=users.txt"]));
+    }
+
+    #[test]
+    fn it_parses_users_file() {
+        assert_eq!(
+            vec!["BobrImperator".to_string(), "mansona".to_string()],
+            parse_users_file("# Ember team\nBobrImperator\n\n  mansona  \n")
+        );
+    }
+
+    #[test]
+    fn it_merges_users() {
+        assert_eq!(
+            vec![
+                "BobrImperator".to_string(),
+                "mansona".to_string(),
+                "Turbo87".to_string()
+            ],
+            merge_users(
+                vec!["BobrImperator".to_string(), "mansona".to_string()],
+                vec!["bobrimperator".to_string(), "Turbo87".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn it_reads_config_from_reader() {
+        let config = r#"{ "labels": [], "header": [], "users": ["BobrImperator"] }"#;
+
+        assert_eq!(
+            FileConfig {
+                users: vec!["BobrImperator".to_string()],
+                ..FileConfig::default()
+            },
+            read_config_from_reader(config.as_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_resolves_sources() {
+        assert_eq!(
+            vec![SourceKind::GitHub],
+            resolve_sources(&FileConfig::default())
+        );
+        assert_eq!(
+            vec![SourceKind::GitHub, SourceKind::Gitea],
+            resolve_sources(&FileConfig {
+                gitea: Some(GiteaConfig::default()),
+                ..FileConfig::default()
+            })
+        );
+        assert_eq!(
+            vec![SourceKind::GitLab],
+            resolve_sources(&FileConfig {
+                sources: Some(vec![SourceKind::GitLab]),
+                gitea: Some(GiteaConfig::default()),
+                ..FileConfig::default()
+            })
+        );
+    }
+
+    #[test]
+    fn it_reads_label_rules() {
+        let config = r#"{
+            "labels": [{
+                "name": "Documentation",
+                "rules": [{ "repo": "ember-cli/*", "title": "docs" }, { "users": ["mansona"] }]
+            }],
+            "header": []
+        }"#;
+
+        assert_eq!(
+            vec![LabelConfig {
+                name: "Documentation".to_string(),
+                repos: vec![],
+                users: vec![],
+                rules: vec![
+                    LabelRule {
+                        repo: Some("ember-cli/*".to_string()),
+                        title: Some("docs".to_string()),
+                        users: vec![],
+                    },
+                    LabelRule {
+                        repo: None,
+                        title: None,
+                        users: vec!["mansona".to_string()],
+                    },
+                ],
+                templates: Templates::default(),
+            }],
+            read_config_from_reader(config.as_bytes()).unwrap().labels
+        );
+    }
+
+    #[test]
+    fn it_validates_config() {
+        let app_params = AppParams {
+            labels: vec![
+                LabelConfig {
+                    name: "Rust".to_string(),
+                    repos: vec!["rust-lang/crates.io".to_string(), "crates.io".to_string()],
+                    users: vec![],
+                    rules: vec![],
+                    templates: Templates::default(),
+                },
+                LabelConfig {
+                    name: "Crates".to_string(),
+                    repos: vec!["rust-lang/crates.io".to_string()],
+                    users: vec![],
+                    rules: vec![],
+                    templates: Templates::default(),
+                },
+                LabelConfig {
+                    name: "Ember".to_string(),
+                    repos: vec![],
+                    users: vec![],
+                    rules: vec![],
+                    templates: Templates::default(),
+                },
+                LabelConfig {
+                    name: "Blueprints".to_string(),
+                    repos: vec![
+                        "simplabs/*".to_string(),
+                        "regex:^simplabs/.*-blueprint$".to_string(),
+                        "regex:(".to_string(),
+                    ],
+                    users: vec![],
+                    rules: vec![],
+                    templates: Templates::default(),
+                },
+            ],
+            exclude: vec!["Turbo87/aprs-parser-rs".to_string(), "Turbo87/".to_string()],
+            tracked_repos: vec!["simplabs".to_string()],
+            unknown_keys: vec!["labels[0].repoz".to_string()],
+            rename: HashMap::from([("atom/keyboard-layout".to_string(), " ".to_string())]),
+            ..AppParams::default()
+        };
+
+        assert_eq!(
+            vec![
+                "rust-lang/crates.io is listed under multiple labels: Rust, Crates.".to_string(),
+                "The label Rust lists a malformed repo \"crates.io\".".to_string(),
+                "The label Ember has no repos.".to_string(),
+                "The label Blueprints lists an invalid pattern \"regex:(\": regex parse error:\n    (\n    ^\nerror: unclosed group".to_string(),
+                "exclude lists a malformed repo \"Turbo87/\".".to_string(),
+                "The config has an unknown key labels[0].repoz.".to_string(),
+                "tracked_repos lists a malformed repo \"simplabs\".".to_string(),
+                "rename has an empty name for atom/keyboard-layout.".to_string(),
+            ],
+            validate_config(&app_params)
+        );
+    }
+
+    #[test]
+    fn it_validates_templates() {
+        let app_params = AppParams {
+            labels: vec![LabelConfig {
+                name: "Ember".to_string(),
+                repos: vec!["emberjs/ember.js".to_string()],
+                users: vec![],
+                rules: vec![],
+                templates: Templates {
+                    item: Some("- {{#if issue_title}}".to_string()),
+                    label: Some("## {{name}}".to_string()),
+                },
+            }],
+            templates: Templates {
+                item: None,
+                label: Some("## {{name".to_string()),
+            },
+            ..AppParams::default()
+        };
+
+        let problems = validate_config(&app_params);
+        assert_eq!(2, problems.len());
+        assert!(problems[0].starts_with("templates has an invalid template: "));
+        assert!(problems[1].starts_with("The label Ember has an invalid template: "));
+    }
+
+    #[test]
+    fn it_finds_duplicate_label_names() {
+        let label = |name: &str| LabelConfig {
+            name: name.to_string(),
+            repos: vec![],
+            users: vec![],
+            rules: vec![],
+            templates: Templates::default(),
+        };
+
+        assert_eq!(
+            vec!["Rust".to_string()],
+            find_duplicate_label_names(&[
+                label("Rust"),
+                label("Ember"),
+                label("Rust"),
+                label("Rust")
+            ])
+        );
+    }
+
+    #[test]
+    fn it_merges_tracked_repos() {
+        let first_config = FileConfig {
+            tracked_repos: vec!["simplabs/qunit-dom".to_string()],
+            ..FileConfig::default()
+        };
+        let second_config = FileConfig {
+            tracked_repos: vec![
+                "simplabs/qunit-dom".to_string(),
+                "simplabs/ember-simple-auth".to_string(),
+            ],
+            ..FileConfig::default()
+        };
+
+        assert_eq!(
+            vec![
+                "simplabs/qunit-dom".to_string(),
+                "simplabs/ember-simple-auth".to_string()
+            ],
+            merge_configs(vec![first_config, second_config]).tracked_repos
+        );
+    }
+
+    #[test]
+    fn it_processes_include_issues_args() {
+        let expected = Args {
+            include_issues: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--include-issues"]));
+    }
+
+    #[test]
+    fn it_processes_only_merged_args() {
+        let expected = Args {
+            only_merged: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--only-merged"]));
+    }
+
+    #[test]
+    fn it_reads_skip_drafts_from_config() {
+        let config = r#"{ "labels": [], "skip_drafts": true }"#;
+
+        assert!(
+            read_config_from_reader(config.as_bytes())
+                .unwrap()
+                .skip_drafts
+        );
+    }
+
+    #[test]
+    fn it_reads_org_from_config() {
+        let config = r#"{ "labels": [], "org": "simplabs" }"#;
+
+        assert_eq!(
+            Some("simplabs".to_string()),
+            read_config_from_reader(config.as_bytes()).unwrap().org
+        );
+    }
+
+    #[test]
+    fn it_reads_skip_bots_from_config() {
+        let config = r#"{ "labels": [], "skip_bots": true }"#;
+
+        assert!(
+            read_config_from_reader(config.as_bytes())
+                .unwrap()
+                .skip_bots
+        );
+    }
+
+    #[test]
+    fn it_reads_exclude_users_from_config() {
+        let config = r#"{ "labels": [], "exclude_users": ["simplabs-bot"] }"#;
+
+        assert_eq!(
+            vec!["simplabs-bot".to_string()],
+            read_config_from_reader(config.as_bytes())
+                .unwrap()
+                .exclude_users
+        );
+    }
+
+    #[test]
+    fn it_reads_auto_labels_from_config() {
+        let config = r#"{
+            "labels": [],
+            "auto_labels": { "topics": { "emberjs": "Ember" }, "languages": { "Rust": "Rust" } }
+        }"#;
+
+        assert_eq!(
+            Some(AutoLabels {
+                topics: BTreeMap::from([("emberjs".to_string(), "Ember".to_string())]),
+                languages: BTreeMap::from([("Rust".to_string(), "Rust".to_string())]),
+            }),
+            read_config_from_reader(config.as_bytes())
+                .unwrap()
+                .auto_labels
+        );
+    }
+
+    #[test]
+    fn it_processes_update_config_args() {
+        assert_eq!(
+            Args {
+                update_config: Some("Ember".to_string()),
+                ..Args::default()
+            },
+            parse_args(&["--update-config=Ember"])
+        );
+        assert_eq!(
+            Args {
+                update_config: Some("".to_string()),
+                ..Args::default()
+            },
+            parse_args(&["--update-config"])
+        );
+    }
+
+    #[test]
+    fn it_processes_exclude_user_args() {
+        let expected = Args {
+            exclude_users: vec![
+                "simplabs-bot".to_string(),
+                "mansona".to_string(),
+                "Turbo87".to_string(),
+            ],
+            ..Args::default()
+        };
+
+        assert_eq!(
+            expected,
+            parse_args(&[
+                "--exclude-user=simplabs-bot,mansona",
+                "--exclude-user",
+                "Turbo87"
+            ])
+        );
+    }
+
+    #[test]
+    fn it_processes_from_and_until_args() {
+        let expected = Args {
+            date: "2024-02-12..2024-02-18".to_string(),
+            date_sign: "".to_string(),
+            from: "2024-02-12".to_string(),
+            until: "2024-02-18".to_string(),
+            after: true,
+            ..Args::default()
+        };
+
+        assert_eq!(
+            expected,
+            parse_args(&["-after", "--from=2024-02-12", "--until=2024-02-18"])
+        );
+    }
+
+    #[test]
+    fn it_builds_open_ended_dates() {
+        assert_eq!(
+            Some(("2024-02-12".to_string(), ">=".to_string())),
+            bounded_date("2024-02-12", "")
+        );
+        assert_eq!(
+            Some(("2024-02-18".to_string(), "<=".to_string())),
+            bounded_date("", "2024-02-18")
+        );
+        assert_eq!(None, bounded_date("", ""));
+    }
+
+    #[test]
+    fn it_processes_week_args() {
+        let expected = Args {
+            date: "2024-02-12..2024-02-18".to_string(),
+            from: "2024-02-12".to_string(),
+            until: "2024-02-18".to_string(),
+            week: "2024-W07".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--week=2024-W7"]));
+    }
+
+    #[test]
+    fn it_resolves_iso_weeks() {
+        let today = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+
+        assert_eq!(
+            Some((
+                "2023-W52".to_string(),
+                NaiveDate::from_ymd_opt(2023, 12, 25).unwrap(),
+                NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+            )),
+            iso_week("last", today)
+        );
+        assert_eq!(
+            Some((
+                "2021-W01".to_string(),
+                NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+                NaiveDate::from_ymd_opt(2021, 1, 10).unwrap()
+            )),
+            iso_week("2021-W01", today)
+        );
+        assert_eq!(None, iso_week("2021-W54", today));
+        assert_eq!(None, iso_week("2021-07", today));
+    }
+
+    #[test]
+    fn it_processes_timezone_args() {
+        let expected = Args {
+            timezone: FixedOffset::east_opt(2 * 3600),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--timezone=+02:00"]));
+    }
+
+    #[test]
+    fn it_processes_group_by_args() {
+        let expected = Args {
+            group_by: GroupBy::Team,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--group-by", "team"]));
+    }
+
+    #[test]
+    fn it_builds_labels_of_teams() {
+        let config = r#"{
+            "labels": [],
+            "teams": { "Frontend": ["BobrImperator", "mansona"], "Backend": ["Turbo87"] }
+        }"#;
+        let teams = read_config_from_reader(config.as_bytes()).unwrap().teams;
+
+        assert_eq!(
+            vec![
+                LabelConfig {
+                    name: "Backend".to_string(),
+                    repos: vec![],
+                    users: vec![],
+                    rules: vec![LabelRule {
+                        users: vec!["Turbo87".to_string()],
+                        ..LabelRule::default()
+                    }],
+                    templates: Templates::default(),
+                },
+                LabelConfig {
+                    name: "Frontend".to_string(),
+                    repos: vec![],
+                    users: vec![],
+                    rules: vec![LabelRule {
+                        users: vec!["BobrImperator".to_string(), "mansona".to_string()],
+                        ..LabelRule::default()
+                    }],
+                    templates: Templates::default(),
+                },
+            ],
+            team_labels(&teams)
+        );
+    }
+
+    #[test]
+    fn it_processes_format_args() {
+        let expected = Args {
+            format: OutputFormat::Html,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--format", "html"]));
+    }
+
+    #[test]
+    fn it_resolves_relative_dates() {
+        let today = NaiveDate::from_ymd_opt(2024, 2, 21).unwrap();
+
+        assert_eq!(
+            Some("2024-02-21".to_string()),
+            relative_date("today", today)
+        );
+        assert_eq!(
+            Some("2024-02-20".to_string()),
+            relative_date("Yesterday", today)
+        );
+        assert_eq!(
+            Some("2024-02-12..2024-02-18".to_string()),
+            relative_date("last week", today)
+        );
+        assert_eq!(
+            Some("2024-02-15..2024-02-21".to_string()),
+            relative_date("past 7 days", today)
+        );
+        assert_eq!(
+            Some("2024-02-21..2024-02-21".to_string()),
+            relative_date("past 1 day", today)
+        );
+        assert_eq!(None, relative_date("past 0 days", today));
+        assert_eq!(None, relative_date("2024-02-21", today));
+    }
+
+    #[test]
+    fn it_processes_stdout_args() {
+        let expected = Args {
+            stdout: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--stdout"]));
+        assert_eq!(expected, parse_args(&["--dry-run"]));
+    }
+
+    #[test]
+    fn it_reads_config_from_toml() {
+        let config = r#"
+users = ["BobrImperator"]
+
+# Engines are grouped with Ember because they're maintained by the same team.
+[[labels]]
+name = "Ember"
+repos = ["emberjs/ember.js", "ember-engines/ember-engines"]
+"#;
+
+        assert_eq!(
+            FileConfig {
+                labels: vec![LabelConfig {
+                    name: "Ember".to_string(),
+                    repos: vec![
+                        "emberjs/ember.js".to_string(),
+                        "ember-engines/ember-engines".to_string()
+                    ],
+                    users: vec![],
+                    rules: vec![],
+                    templates: Templates::default(),
+                }],
+                users: vec!["BobrImperator".to_string()],
+                ..FileConfig::default()
+            },
+            read_config_from_toml(config).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_reads_config_in_the_given_format() {
+        let config = read_config_in_format(
+            "labels = []\nusers = [\"BobrImperator\"]".as_bytes(),
+            &ConfigFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(vec!["BobrImperator".to_string()], config.users);
+
+        let config = read_config_in_format(
+            "labels: []\nusers: [BobrImperator]".as_bytes(),
+            &ConfigFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(vec!["BobrImperator".to_string()], config.users);
+
+        assert!(read_config_in_format("labels: []".as_bytes(), &ConfigFormat::Json).is_err());
+    }
+
+    #[test]
+    fn it_processes_config_format_args() {
+        let expected = Args {
+            config_format: ConfigFormat::Yaml,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["--config-format=yaml"]));
+    }
+
+    #[test]
+    fn it_reads_config_from_yaml_with_anchors() {
+        let config = r#"
+labels:
+  - name: Ember
+    repos: &ember_repos
+      - emberjs/ember.js
+      - ember-engines/ember-engines
+  - name: Ember Addons
+    repos: *ember_repos
+    users: [BobrImperator]
+"#;
+        let repos = vec![
+            "emberjs/ember.js".to_string(),
+            "ember-engines/ember-engines".to_string(),
+        ];
+
+        assert_eq!(
+            FileConfig {
+                labels: vec![
+                    LabelConfig {
+                        name: "Ember".to_string(),
+                        repos: repos.clone(),
+                        users: vec![],
+                        rules: vec![],
+                        templates: Templates::default(),
+                    },
+                    LabelConfig {
+                        name: "Ember Addons".to_string(),
+                        repos,
+                        users: vec!["BobrImperator".to_string()],
+                        rules: vec![],
+                        templates: Templates::default(),
+                    }
+                ],
+                ..FileConfig::default()
+            },
+            read_config_from_yaml(config).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_processes_validate_subcommand() {
+        let expected = Args {
+            validate: true,
+            schema: true,
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["validate", "--schema"]));
+    }
+
+    #[test]
+    fn it_processes_publish_subcommand() {
+        let expected = Args {
+            publish: Some(PublishTarget::Slack),
+            date: "2024-02-12".to_string(),
+            ..Args::default()
+        };
+
+        assert_eq!(
+            expected,
+            parse_args(&["publish", "slack", "--date=2024-02-12"])
+        );
+    }
+
+    #[test]
+    fn it_collects_unknown_keys_of_config() {
+        let config = r#"{ "labels": [{ "name": "Ember", "repoz": [] }], "exlude": [] }"#;
+
+        assert_eq!(
+            vec!["exlude".to_string(), "labels[0].repoz".to_string()],
+            read_config_from_reader(config.as_bytes())
+                .unwrap()
+                .unknown_keys
+        );
+    }
+
+    #[test]
+    fn it_processes_init_subcommand() {
+        let expected = Args {
+            init: true,
+            users: vec!["BobrImperator".to_string()],
+            config_paths: vec!["twios.toml".to_string()],
+            ..Args::default()
+        };
+
+        assert_eq!(
+            expected,
+            parse_args(&["init", "--users=BobrImperator", "--config-path=twios.toml"])
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_args() {
+        assert!(parse_cli_args(["twios", "--exlude-drafts"].map(String::from)).is_err());
+        assert!(parse_cli_args(["twios", "--sort=stars"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn it_processes_generate_subcommand() {
+        let expected = Args {
+            users: vec!["BobrImperator".to_string()],
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["generate", "--users=BobrImperator"]));
+    }
+
+    #[test]
+    fn it_processes_completions_subcommand() {
+        let expected = Args {
+            completions: Some(Shell::Zsh),
+            ..Args::default()
+        };
+
+        assert_eq!(expected, parse_args(&["completions", "zsh"]));
+    }
+
+    #[test]
+    fn it_writes_completions() {
+        let mut script = vec![];
+        write_completions(Shell::Bash, &mut script);
+
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("--include-reviews"));
+        assert!(script.contains("validate"));
+    }
 }
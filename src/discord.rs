@@ -0,0 +1,146 @@
+use crate::chat::{self, Syntax};
+use serde_json::{json, Value};
+
+// Discord rejects messages above these limits.
+const MAX_CONTENT_LENGTH: usize = 2000;
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_EMBEDS: usize = 10;
+const MAX_EMBEDS_LENGTH: usize = 6000;
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+const MARKDOWN: Syntax = Syntax {
+    link: |text, url| format!("[{}]({})", text, url),
+    escape: escape_markdown,
+    code: |code| format!("`{}`", code),
+    strong: "**",
+    emphasis: "_",
+};
+
+fn push_embeds(messages: &mut Vec<Value>, embeds: &mut Vec<Value>, embeds_length: &mut usize) {
+    if !embeds.is_empty() {
+        messages.push(json!({ "embeds": embeds }));
+    }
+    embeds.clear();
+    *embeds_length = 0;
+}
+
+/// Converts a markdown document to webhook messages, with an embed for each section and the text
+/// before the first heading as content, split to stay below the length limits.
+pub fn messages(markdown: &str) -> Vec<Value> {
+    let mut messages = vec![];
+    let mut embeds: Vec<Value> = vec![];
+    let mut embeds_length = 0;
+
+    for section in chat::sections(markdown, &MARKDOWN) {
+        let heading = match &section.heading {
+            Some(heading) => chat::truncate(heading, MAX_TITLE_LENGTH),
+            None => {
+                push_embeds(&mut messages, &mut embeds, &mut embeds_length);
+                for content in chat::split_text(&section.text, MAX_CONTENT_LENGTH) {
+                    messages.push(json!({ "content": content }));
+                }
+                continue;
+            }
+        };
+
+        let mut descriptions = chat::split_text(&section.text, MAX_DESCRIPTION_LENGTH);
+        if descriptions.is_empty() {
+            descriptions.push(String::new());
+        }
+
+        for description in descriptions {
+            let length = heading.chars().count() + description.chars().count();
+            if embeds.len() == MAX_EMBEDS || embeds_length + length > MAX_EMBEDS_LENGTH {
+                push_embeds(&mut messages, &mut embeds, &mut embeds_length);
+            }
+            embeds.push(json!({ "title": heading, "description": description }));
+            embeds_length += length;
+        }
+    }
+
+    push_embeds(&mut messages, &mut embeds, &mut embeds_length);
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_markdown_to_messages() {
+        let markdown = "This week we contributed to:\n\n## Ember\n\n- [ember-engines/ember-engines] [#798](https://github.com/ember-engines/ember-engines/pull/798) Ember 4 *compatibility* ([@Bobr_Imperator])\n\n## Rust\n\n- [#1](https://github.com/rust-lang/crates.io/pull/1) Update `nan`\n\n[ember-engines/ember-engines]: https://github.com/ember-engines/ember-engines\n[@Bobr_Imperator]: https://github.com/Bobr_Imperator\n";
+
+        assert_eq!(
+            vec![
+                json!({ "content": "This week we contributed to:" }),
+                json!({ "embeds": [
+                    {
+                        "title": "Ember",
+                        "description": "• [ember-engines/ember-engines](https://github.com/ember-engines/ember-engines) [#798](https://github.com/ember-engines/ember-engines/pull/798) Ember 4 _compatibility_ ([@Bobr\\_Imperator](https://github.com/Bobr_Imperator))"
+                    },
+                    {
+                        "title": "Rust",
+                        "description": "• [#1](https://github.com/rust-lang/crates.io/pull/1) Update `nan`"
+                    }
+                ]}),
+            ],
+            messages(markdown)
+        );
+    }
+
+    #[test]
+    fn it_splits_long_reports() {
+        let long_content = format!("{}\n", "a".repeat(1500)).repeat(2);
+        let mut markdown = long_content.replace('\n', "\n\n");
+        for i in 0..12 {
+            markdown.push_str(&format!("## Label {}\n\n- {}\n\n", i, "b".repeat(100)));
+        }
+        markdown.push_str(&format!("## Long\n\n{}", "- c\n".repeat(2000)));
+
+        let messages = messages(&markdown);
+
+        assert_eq!(
+            2,
+            messages
+                .iter()
+                .filter(|message| message["content"].is_string())
+                .count()
+        );
+        for message in messages
+            .iter()
+            .filter_map(|message| message["embeds"].as_array())
+        {
+            assert!(message.len() <= MAX_EMBEDS);
+            let length: usize = message
+                .iter()
+                .map(|embed| {
+                    embed["title"].as_str().unwrap().chars().count()
+                        + embed["description"].as_str().unwrap().chars().count()
+                })
+                .sum();
+            assert!(length <= MAX_EMBEDS_LENGTH);
+        }
+        assert_eq!(
+            14,
+            messages
+                .iter()
+                .filter_map(|message| message["embeds"].as_array())
+                .map(|embeds| embeds.len())
+                .sum::<usize>()
+        );
+    }
+}
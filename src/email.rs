@@ -0,0 +1,150 @@
+use crate::Item;
+
+// Email clients drop <style> elements, so every element is styled inline.
+const INLINE_STYLES: [(&str, &str); 8] = [
+    ("<h1>", "<h1 style=\"font-size:24px;margin:0 0 16px;\">"),
+    (
+        "<h2>",
+        "<h2 style=\"font-size:20px;margin:24px 0 8px;padding-bottom:4px;border-bottom:1px solid #eaecef;\">",
+    ),
+    ("<h3>", "<h3 style=\"font-size:16px;margin:16px 0 8px;\">"),
+    ("<p>", "<p style=\"margin:0 0 16px;\">"),
+    ("<ul>", "<ul style=\"margin:0 0 16px;padding-left:24px;\">"),
+    ("<li>", "<li style=\"margin:0 0 4px;\">"),
+    (
+        "<a href=",
+        "<a style=\"color:#0366d6;text-decoration:none;\" href=",
+    ),
+    (
+        "<code>",
+        "<code style=\"font-family:monospace;background-color:#f6f8fa;padding:2px 4px;\">",
+    ),
+];
+
+/// Wraps the HTML of a report in an email body with inline styles and a centered 600px column.
+pub fn html(title: &str, body: &str) -> String {
+    let body = INLINE_STYLES
+        .iter()
+        .fold(body.to_string(), |body, (tag, styled_tag)| {
+            body.replace(tag, styled_tag)
+        });
+
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{}</title>
+</head>
+<body style=\"margin:0;padding:0;background-color:#f6f8fa;\">
+<table role=\"presentation\" width=\"100%\" cellpadding=\"0\" cellspacing=\"0\" style=\"background-color:#f6f8fa;\">
+<tr><td align=\"center\" style=\"padding:24px 12px;\">
+<table role=\"presentation\" width=\"600\" cellpadding=\"0\" cellspacing=\"0\" style=\"max-width:600px;background-color:#ffffff;\">
+<tr><td style=\"padding:24px;font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;font-size:16px;line-height:1.5;color:#24292e;\">
+{}</td></tr>
+</table>
+</td></tr>
+</table>
+</body>
+</html>
+",
+        title.replace('&', "&amp;").replace('<', "&lt;"),
+        body
+    )
+}
+
+fn underline(text: &str, character: &str) -> String {
+    character.repeat(text.chars().count())
+}
+
+/// Formats the plain text alternative of the email, with a heading per section and the url
+/// below each item.
+pub fn plain_text(title: &str, header: &[String], sections: &[(String, Vec<Item>)]) -> String {
+    let mut lines = vec![title.to_string(), underline(title, "="), String::from("")];
+
+    if !header.is_empty() {
+        lines.extend(header.iter().cloned());
+        lines.push(String::from(""));
+    }
+
+    for (heading, items) in sections {
+        lines.push(heading.clone());
+        lines.push(underline(heading, "-"));
+        lines.push(String::from(""));
+        for item in items {
+            lines.push(format!(
+                "- {} ({}#{}) by {}",
+                item.issue_title,
+                item.full_repository_name,
+                item.issue_number,
+                item.user_name.as_ref().unwrap_or(&item.user_login)
+            ));
+            lines.push(format!("  {}", item.issue_url));
+        }
+        lines.push(String::from(""));
+    }
+
+    format!("{}\n", lines.join("\n").trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ItemKind, ItemMergeStatus, ItemRole};
+
+    #[test]
+    fn it_inlines_styles() {
+        let email = html(
+            "Ember & Rust",
+            "<h2>Ember</h2>\n<ul>\n<li><a href=\"https://github.com/BobrImperator\">@BobrImperator</a></li>\n</ul>\n",
+        );
+
+        assert!(email.contains("<title>Ember &amp; Rust</title>"));
+        assert!(email.contains("<h2 style=\"font-size:20px;"));
+        assert!(email.contains("<li style=\"margin:0 0 4px;\"><a style=\"color:#0366d6;text-decoration:none;\" href=\"https://github.com/BobrImperator\">"));
+        assert!(!email.contains("<ul>"));
+    }
+
+    #[test]
+    fn it_formats_plain_text() {
+        let item = Item {
+            issue_number: "63".to_string(),
+            issue_title: "Update nan".to_string(),
+            issue_url: "https://github.com/atom/keyboard-layout/pull/63".to_string(),
+            organization_name: "atom".to_string(),
+            repository_name: "keyboard-layout".to_string(),
+            full_repository_name: "atom/keyboard-layout".to_string(),
+            original_repository_name: None,
+            repository_url: "https://github.com/atom/keyboard-layout".to_string(),
+            user_login: "mansona".to_string(),
+            user_url: "https://github.com/mansona".to_string(),
+            user_name: None,
+            state: "closed".to_string(),
+            merge_status: ItemMergeStatus::Merged,
+            role: ItemRole::Author,
+            kind: ItemKind::PullRequest,
+            created_at: "2021-12-01T10:00:00Z".parse().unwrap(),
+            co_authors: vec![],
+        };
+
+        assert_eq!(
+            "This week
+=========
+
+Our contributions:
+
+Atom
+----
+
+- Update nan (atom/keyboard-layout#63) by mansona
+  https://github.com/atom/keyboard-layout/pull/63
+",
+            plain_text(
+                "This week",
+                &["Our contributions:".to_string()],
+                &[("Atom".to_string(), vec![item])]
+            )
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use thiserror::Error;
+
+/// Failures that abort a run, each with its own exit code so scripts can tell them apart.
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("Your config file could not be found: {0}")]
+    ConfigNotFound(String),
+    #[error("There was a problem reading your config file, check if it's correct and valid: {0}")]
+    ConfigParse(String),
+    #[error("The GitHub API request failed: {0}")]
+    GitHub(String),
+    #[error(
+        "No users were provided.\n\
+         Add them to the `users` list of your config file or pass them with --users=user1,user2.\n\
+         Alternatively use --org=name to query every PR of an organization."
+    )]
+    NoUsers,
+    #[error("Aborting because of --strict.")]
+    InconsistentConfig,
+    #[error("{0}")]
+    Write(String),
+    #[error("{0}")]
+    Exec(String),
+    #[error("Could not read the users file {0}")]
+    UsersFile(String),
+    #[error("The config has {0} problem(s).")]
+    InvalidConfig(usize),
+    #[error("{0}")]
+    Usage(String),
+    #[error("Could not publish the report: {0}")]
+    Publish(String),
+    #[error("The {0} API request failed: {1}")]
+    Source(String, String),
+    /// The report was written, but without the PRs of the users skipped with `continue_on_error`.
+    #[error("The report is incomplete, {0} user(s) were skipped because of errors.")]
+    PartialFailure(usize),
+}
+
+impl AppError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::ConfigNotFound(_) => 2,
+            AppError::ConfigParse(_) => 3,
+            AppError::GitHub(_) => 4,
+            AppError::NoUsers => 5,
+            AppError::InconsistentConfig => 6,
+            AppError::Write(_) => 7,
+            AppError::Exec(_) => 8,
+            AppError::UsersFile(_) => 9,
+            AppError::InvalidConfig(_) => 10,
+            AppError::Usage(_) => 11,
+            AppError::Publish(_) => 12,
+            AppError::Source(..) => 13,
+            AppError::PartialFailure(_) => 14,
+        }
+    }
+}
+
+impl From<octocrab::Error> for AppError {
+    fn from(error: octocrab::Error) -> AppError {
+        AppError::GitHub(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn it_uses_distinct_exit_codes() {
+        let errors = [
+            AppError::ConfigNotFound("".to_string()),
+            AppError::ConfigParse("".to_string()),
+            AppError::NoUsers,
+            AppError::InconsistentConfig,
+            AppError::Write("".to_string()),
+            AppError::Exec("".to_string()),
+            AppError::UsersFile("".to_string()),
+            AppError::InvalidConfig(1),
+            AppError::Usage("".to_string()),
+            AppError::Publish("".to_string()),
+            AppError::Source("".to_string(), "".to_string()),
+            AppError::PartialFailure(1),
+        ];
+        let exit_codes = errors
+            .iter()
+            .map(|error| error.exit_code())
+            .collect::<HashSet<i32>>();
+
+        assert_eq!(errors.len(), exit_codes.len());
+        assert!(!exit_codes.contains(&0));
+        assert!(!exit_codes.contains(&1));
+    }
+
+    #[test]
+    fn it_describes_errors() {
+        assert_eq!(
+            "The GitLab API request failed: 401 Unauthorized",
+            AppError::Source("GitLab".to_string(), "401 Unauthorized".to_string()).to_string()
+        );
+        assert_eq!(
+            "The report is incomplete, 2 user(s) were skipped because of errors.",
+            AppError::PartialFailure(2).to_string()
+        );
+    }
+}
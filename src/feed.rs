@@ -0,0 +1,203 @@
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A report added to an RSS or Atom feed.
+pub struct FeedEntry {
+    pub title: String,
+    /// Identifies the entry, so a report of the same date replaces its previous entry.
+    pub id: String,
+    pub published: DateTime<Utc>,
+    pub content: String,
+}
+
+const NEW_FEED: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<rss version=\"2.0\">
+<channel>
+<title>This week in open source</title>
+<description>The open source contributions of the week.</description>
+</channel>
+</rss>
+";
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wraps the text in CDATA, splitting the `]]>` it contains across two sections.
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn is_atom(feed: &str) -> bool {
+    feed.contains("<feed")
+}
+
+fn format_entry(feed: &str, entry: &FeedEntry) -> String {
+    if is_atom(feed) {
+        format!(
+            "<entry>\n<title>{}</title>\n<id>{}</id>\n<updated>{}</updated>\n<content type=\"html\">{}</content>\n</entry>\n",
+            escape_xml(&entry.title),
+            escape_xml(&entry.id),
+            entry.published.to_rfc3339(),
+            cdata(&entry.content)
+        )
+    } else {
+        format!(
+            "<item>\n<title>{}</title>\n<guid isPermaLink=\"false\">{}</guid>\n<pubDate>{}</pubDate>\n<description>{}</description>\n</item>\n",
+            escape_xml(&entry.title),
+            escape_xml(&entry.id),
+            entry.published.to_rfc2822(),
+            cdata(&entry.content)
+        )
+    }
+}
+
+/// Removes the element that contains the `marker`, e.g. the `<item>` of a guid.
+fn remove_element(feed: &str, start_tag: &str, end_tag: &str, marker: &str) -> String {
+    let marker_index = match feed.find(marker) {
+        Some(index) => index,
+        None => return feed.to_string(),
+    };
+    let start = feed[..marker_index].rfind(start_tag);
+    let end = feed[marker_index..]
+        .find(end_tag)
+        .map(|index| marker_index + index + end_tag.len());
+
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            let end = if feed[end..].starts_with('\n') {
+                end + 1
+            } else {
+                end
+            };
+            format!("{}{}", &feed[..start], &feed[end..])
+        }
+        _ => feed.to_string(),
+    }
+}
+
+/// Adds the entry before the newest one of the feed, replacing an entry with the same id.
+pub fn add_entry(feed: &str, entry: &FeedEntry) -> String {
+    let (start_tag, end_tag, marker, closing_tag) = if is_atom(feed) {
+        (
+            "<entry>",
+            "</entry>",
+            format!("<id>{}</id>", escape_xml(&entry.id)),
+            "</feed>",
+        )
+    } else {
+        (
+            "<item>",
+            "</item>",
+            format!(
+                "<guid isPermaLink=\"false\">{}</guid>",
+                escape_xml(&entry.id)
+            ),
+            "</channel>",
+        )
+    };
+
+    let feed = remove_element(feed, start_tag, end_tag, &marker);
+    let index = feed
+        .find(start_tag)
+        .or_else(|| feed.rfind(closing_tag))
+        .unwrap_or(feed.len());
+
+    format!(
+        "{}{}{}",
+        &feed[..index],
+        format_entry(&feed, entry),
+        &feed[index..]
+    )
+}
+
+/// Adds the entry to the feed file, creating an RSS feed when it's missing.
+pub fn write_entry<P: AsRef<Path>>(path: P, entry: &FeedEntry) -> io::Result<()> {
+    let feed = match fs::read_to_string(&path) {
+        Ok(feed) => feed,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => NEW_FEED.to_string(),
+        Err(error) => return Err(error),
+    };
+
+    fs::write(path, add_entry(&feed, entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_helper(content: &str) -> FeedEntry {
+        FeedEntry {
+            title: "This week in open source 2024-01-07".to_string(),
+            id: "2024-01-07".to_string(),
+            published: "2024-01-07T10:00:00Z".parse().unwrap(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_adds_items_to_rss_feeds() {
+        let feed = add_entry(NEW_FEED, &entry_helper("<h2>Ember</h2>"));
+
+        assert_eq!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<rss version=\"2.0\">
+<channel>
+<title>This week in open source</title>
+<description>The open source contributions of the week.</description>
+<item>
+<title>This week in open source 2024-01-07</title>
+<guid isPermaLink=\"false\">2024-01-07</guid>
+<pubDate>Sun, 7 Jan 2024 10:00:00 +0000</pubDate>
+<description><![CDATA[<h2>Ember</h2>]]></description>
+</item>
+</channel>
+</rss>
+",
+            feed
+        );
+    }
+
+    #[test]
+    fn it_replaces_items_with_the_same_id() {
+        let feed = add_entry(NEW_FEED, &entry_helper("<h2>Ember</h2>"));
+        let feed = add_entry(&feed, &entry_helper("<h2>Rust</h2>"));
+
+        assert_eq!(1, feed.matches("<item>").count());
+        assert!(feed.contains("<![CDATA[<h2>Rust</h2>]]>"));
+    }
+
+    #[test]
+    fn it_adds_entries_before_the_newest_atom_entry() {
+        let feed = "<feed xmlns=\"http://www.w3.org/2005/Atom\">
+<title>Blog</title>
+<entry>
+<id>older</id>
+</entry>
+</feed>
+";
+        let feed = add_entry(feed, &entry_helper("a ]]> b"));
+
+        assert_eq!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">
+<title>Blog</title>
+<entry>
+<title>This week in open source 2024-01-07</title>
+<id>2024-01-07</id>
+<updated>2024-01-07T10:00:00+00:00</updated>
+<content type=\"html\"><![CDATA[a ]]]]><![CDATA[> b]]></content>
+</entry>
+<entry>
+<id>older</id>
+</entry>
+</feed>
+",
+            feed
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::cli::GiteaConfig;
+use crate::error::AppError;
+use crate::source::Source;
+use crate::{Item, ItemKind, ItemMergeStatus, ItemRole};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use futures::future::{BoxFuture, FutureExt};
+use serde::Deserialize;
+use std::env;
+
+const PER_PAGE: u32 = 50;
+
+#[derive(Deserialize, Debug)]
+struct Issue {
+    number: u64,
+    title: String,
+    html_url: String,
+    /// `open` or `closed`.
+    state: String,
+    created_at: DateTime<Utc>,
+    user: User,
+    pull_request: Option<PullRequestMeta>,
+}
+
+#[derive(Deserialize, Debug)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct PullRequestMeta {
+    #[serde(default)]
+    merged: bool,
+}
+
+fn pulls_url(
+    base_url: &str,
+    repo: &str,
+    user: &str,
+    range: Option<(NaiveDate, NaiveDate)>,
+    page: u32,
+) -> String {
+    let mut url = format!(
+        "{}/api/v1/repos/{}/issues?type=pulls&state=all&created_by={}&limit={}&page={}",
+        base_url.trim_end_matches('/'),
+        repo,
+        user,
+        PER_PAGE,
+        page
+    );
+    // `since` filters by the last update, which is never before the creation.
+    if let Some((from, _)) = range {
+        url.push_str(&format!("&since={}T00:00:00Z", from));
+    }
+
+    url
+}
+
+fn is_in_range(created_at: DateTime<Utc>, range: Option<(NaiveDate, NaiveDate)>) -> bool {
+    match range {
+        Some((from, to)) => {
+            let created_on = created_at.date_naive();
+            from <= created_on && created_on < to + Duration::days(1)
+        }
+        None => true,
+    }
+}
+
+fn item_from_issue(base_url: &str, repo: &str, issue: Issue) -> Option<Item> {
+    let (organization_name, repository_name) = repo.split_once('/')?;
+    let base_url = base_url.trim_end_matches('/');
+    let merge_status = match (&issue.pull_request, issue.state.as_str()) {
+        (Some(pull_request), _) if pull_request.merged => ItemMergeStatus::Merged,
+        (Some(_), "closed") => ItemMergeStatus::NotMerged,
+        _ => ItemMergeStatus::Unknown,
+    };
+
+    Some(Item {
+        issue_number: issue.number.to_string(),
+        issue_title: issue.title,
+        issue_url: issue.html_url,
+        organization_name: organization_name.to_string(),
+        repository_name: repository_name.to_string(),
+        full_repository_name: repo.to_string(),
+        original_repository_name: None,
+        repository_url: format!("{}/{}", base_url, repo),
+        user_url: format!("{}/{}", base_url, issue.user.login),
+        user_name: None,
+        user_login: issue.user.login,
+        state: issue.state,
+        merge_status,
+        role: ItemRole::Author,
+        kind: ItemKind::PullRequest,
+        created_at: issue.created_at,
+        co_authors: vec![],
+    })
+}
+
+/// Fetches the PRs the user created in the date range on each of the repositories, Gitea can't
+/// search them across the whole instance.
+async fn get_items_for_user(
+    config: &GiteaConfig,
+    token: Option<&str>,
+    user: &str,
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Vec<Item>, String> {
+    let client = reqwest::Client::new();
+    let mut items = vec![];
+
+    for repo in &config.repos {
+        let mut page = 1;
+        loop {
+            let url = pulls_url(&config.base_url, repo, user, range, page);
+            let mut request = client.get(&url);
+            if let Some(token) = token {
+                request = request.header("Authorization", format!("token {}", token));
+            }
+
+            let response = request.send().await.map_err(|error| error.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("{} responded with {}", url, response.status()));
+            }
+
+            let issues: Vec<Issue> =
+                serde_json::from_str(&response.text().await.map_err(|error| error.to_string())?)
+                    .map_err(|error| error.to_string())?;
+            let count = issues.len();
+            items.extend(
+                issues
+                    .into_iter()
+                    .filter(|issue| is_in_range(issue.created_at, range))
+                    .filter_map(|issue| item_from_issue(&config.base_url, repo, issue)),
+            );
+
+            if count < PER_PAGE as usize {
+                break;
+            }
+            page += 1;
+        }
+    }
+
+    Ok(items)
+}
+
+impl Source for GiteaConfig {
+    fn name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    fn users(&self) -> &[String] {
+        &self.users
+    }
+
+    fn fetch_items<'a>(
+        &'a self,
+        user: &'a str,
+        range: Option<(NaiveDate, NaiveDate)>,
+    ) -> BoxFuture<'a, Result<Vec<Item>, AppError>> {
+        async move {
+            let token = self.token.clone().or_else(|| env::var("GITEA_TOKEN").ok());
+            get_items_for_user(self, token.as_deref(), user, range)
+                .await
+                .map_err(|error| AppError::Source(self.name().to_string(), error))
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range_helper() -> Option<(NaiveDate, NaiveDate)> {
+        Some((
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 7).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn it_builds_pulls_urls() {
+        assert_eq!(
+            "https://codeberg.org/api/v1/repos/simplabs/qunit-dom/issues?type=pulls&state=all&created_by=BobrImperator&limit=50&page=2&since=2024-01-01T00:00:00Z",
+            pulls_url(
+                "https://codeberg.org/",
+                "simplabs/qunit-dom",
+                "BobrImperator",
+                range_helper(),
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn it_keeps_pulls_created_in_the_range() {
+        assert!(is_in_range(
+            "2024-01-07T23:00:00Z".parse().unwrap(),
+            range_helper()
+        ));
+        assert!(!is_in_range(
+            "2024-01-08T00:00:00Z".parse().unwrap(),
+            range_helper()
+        ));
+        assert!(!is_in_range(
+            "2023-12-31T23:00:00Z".parse().unwrap(),
+            range_helper()
+        ));
+        assert!(is_in_range("2008-01-01T00:00:00Z".parse().unwrap(), None));
+    }
+
+    #[test]
+    fn it_converts_pulls_to_items() {
+        let issue: Issue = serde_json::from_str(
+            r#"{
+                "number": 5,
+                "title": "Support Gitea",
+                "html_url": "https://codeberg.org/simplabs/qunit-dom/pulls/5",
+                "state": "closed",
+                "created_at": "2024-01-03T10:00:00+01:00",
+                "user": { "login": "BobrImperator" },
+                "pull_request": { "merged": true }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Item {
+                issue_number: "5".to_string(),
+                issue_title: "Support Gitea".to_string(),
+                issue_url: "https://codeberg.org/simplabs/qunit-dom/pulls/5".to_string(),
+                organization_name: "simplabs".to_string(),
+                repository_name: "qunit-dom".to_string(),
+                full_repository_name: "simplabs/qunit-dom".to_string(),
+                original_repository_name: None,
+                repository_url: "https://codeberg.org/simplabs/qunit-dom".to_string(),
+                user_login: "BobrImperator".to_string(),
+                user_url: "https://codeberg.org/BobrImperator".to_string(),
+                user_name: None,
+                state: "closed".to_string(),
+                merge_status: ItemMergeStatus::Merged,
+                role: ItemRole::Author,
+                kind: ItemKind::PullRequest,
+                created_at: "2024-01-03T09:00:00Z".parse().unwrap(),
+                co_authors: vec![],
+            }),
+            item_from_issue("https://codeberg.org/", "simplabs/qunit-dom", issue)
+        );
+    }
+}
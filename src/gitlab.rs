@@ -0,0 +1,225 @@
+use crate::cli::GitLabConfig;
+use crate::error::AppError;
+use crate::source::Source;
+use crate::{Item, ItemKind, ItemMergeStatus, ItemRole};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use futures::future::{BoxFuture, FutureExt};
+use serde::Deserialize;
+use std::env;
+
+const PER_PAGE: u32 = 100;
+
+#[derive(Deserialize, Debug)]
+struct MergeRequest {
+    iid: u64,
+    title: String,
+    web_url: String,
+    /// `opened`, `closed`, `locked` or `merged`.
+    state: String,
+    created_at: DateTime<Utc>,
+    author: Author,
+}
+
+#[derive(Deserialize, Debug)]
+struct Author {
+    username: String,
+    web_url: String,
+}
+
+fn merge_requests_url(
+    base_url: &str,
+    user: &str,
+    range: Option<(NaiveDate, NaiveDate)>,
+    page: u32,
+) -> String {
+    let mut url = format!(
+        "{}/api/v4/merge_requests?scope=all&author_username={}&per_page={}&page={}",
+        base_url.trim_end_matches('/'),
+        user,
+        PER_PAGE,
+        page
+    );
+    if let Some((from, to)) = range {
+        url.push_str(&format!(
+            "&created_after={}T00:00:00Z&created_before={}T00:00:00Z",
+            from,
+            to + Duration::days(1)
+        ));
+    }
+
+    url
+}
+
+/// Merge requests of nested groups keep the whole group path as their organization,
+/// e.g. `gitlab-org/ci-cd` of `https://gitlab.com/gitlab-org/ci-cd/runner/-/merge_requests/1`.
+fn item_from_merge_request(merge_request: MergeRequest) -> Option<Item> {
+    let (repository_url, _) = merge_request.web_url.split_once("/-/merge_requests/")?;
+    let (base_url, path) = repository_url.split_once("://")?;
+    let (_, full_repository_name) = path.split_once('/')?;
+    let (organization_name, repository_name) = full_repository_name.rsplit_once('/')?;
+    let (state, merge_status) = match merge_request.state.as_str() {
+        "merged" => ("closed", ItemMergeStatus::Merged),
+        "closed" | "locked" => ("closed", ItemMergeStatus::NotMerged),
+        _ => ("open", ItemMergeStatus::Unknown),
+    };
+
+    Some(Item {
+        issue_number: merge_request.iid.to_string(),
+        issue_title: merge_request.title,
+        issue_url: merge_request.web_url.clone(),
+        organization_name: organization_name.to_string(),
+        repository_name: repository_name.to_string(),
+        full_repository_name: full_repository_name.to_string(),
+        original_repository_name: None,
+        repository_url: format!("{}://{}", base_url, path),
+        user_login: merge_request.author.username,
+        user_url: merge_request.author.web_url,
+        user_name: None,
+        state: state.to_string(),
+        merge_status,
+        role: ItemRole::Author,
+        kind: ItemKind::PullRequest,
+        created_at: merge_request.created_at,
+        co_authors: vec![],
+    })
+}
+
+/// Fetches the merge requests the user created in the date range, on all projects of the instance.
+async fn get_items_for_user(
+    base_url: &str,
+    token: Option<&str>,
+    user: &str,
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> Result<Vec<Item>, String> {
+    let client = reqwest::Client::new();
+    let mut items = vec![];
+    let mut page = 1;
+
+    loop {
+        let url = merge_requests_url(base_url, user, range, page);
+        let mut request = client.get(&url);
+        if let Some(token) = token {
+            request = request.header("PRIVATE-TOKEN", token);
+        }
+
+        let response = request.send().await.map_err(|error| error.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("{} responded with {}", url, response.status()));
+        }
+
+        let merge_requests: Vec<MergeRequest> =
+            serde_json::from_str(&response.text().await.map_err(|error| error.to_string())?)
+                .map_err(|error| error.to_string())?;
+        let count = merge_requests.len();
+        items.extend(
+            merge_requests
+                .into_iter()
+                .filter_map(item_from_merge_request),
+        );
+
+        if count < PER_PAGE as usize {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(items)
+}
+
+impl Source for GitLabConfig {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn users(&self) -> &[String] {
+        &self.users
+    }
+
+    fn fetch_items<'a>(
+        &'a self,
+        user: &'a str,
+        range: Option<(NaiveDate, NaiveDate)>,
+    ) -> BoxFuture<'a, Result<Vec<Item>, AppError>> {
+        async move {
+            let token = self.token.clone().or_else(|| env::var("GITLAB_TOKEN").ok());
+            get_items_for_user(&self.base_url, token.as_deref(), user, range)
+                .await
+                .map_err(|error| AppError::Source(self.name().to_string(), error))
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_merge_requests_urls() {
+        let range = Some((
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 7).unwrap(),
+        ));
+
+        assert_eq!(
+            "https://gitlab.example.com/api/v4/merge_requests?scope=all&author_username=BobrImperator&per_page=100&page=2&created_after=2024-01-01T00:00:00Z&created_before=2024-01-08T00:00:00Z",
+            merge_requests_url("https://gitlab.example.com/", "BobrImperator", range, 2)
+        );
+    }
+
+    #[test]
+    fn it_converts_merge_requests_to_items() {
+        let merge_request: MergeRequest = serde_json::from_str(
+            r#"{
+                "iid": 12,
+                "title": "Support Ruby 3.3",
+                "web_url": "https://gitlab.com/gitlab-org/ci-cd/runner/-/merge_requests/12",
+                "state": "merged",
+                "created_at": "2024-01-03T10:00:00.000Z",
+                "author": { "username": "BobrImperator", "web_url": "https://gitlab.com/BobrImperator" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Item {
+                issue_number: "12".to_string(),
+                issue_title: "Support Ruby 3.3".to_string(),
+                issue_url: "https://gitlab.com/gitlab-org/ci-cd/runner/-/merge_requests/12"
+                    .to_string(),
+                organization_name: "gitlab-org/ci-cd".to_string(),
+                repository_name: "runner".to_string(),
+                full_repository_name: "gitlab-org/ci-cd/runner".to_string(),
+                original_repository_name: None,
+                repository_url: "https://gitlab.com/gitlab-org/ci-cd/runner".to_string(),
+                user_login: "BobrImperator".to_string(),
+                user_url: "https://gitlab.com/BobrImperator".to_string(),
+                user_name: None,
+                state: "closed".to_string(),
+                merge_status: ItemMergeStatus::Merged,
+                role: ItemRole::Author,
+                kind: ItemKind::PullRequest,
+                created_at: "2024-01-03T10:00:00Z".parse().unwrap(),
+                co_authors: vec![],
+            }),
+            item_from_merge_request(merge_request)
+        );
+    }
+
+    #[test]
+    fn it_skips_merge_requests_with_unexpected_urls() {
+        let merge_request = MergeRequest {
+            iid: 1,
+            title: "Fix".to_string(),
+            web_url: "https://gitlab.com/BobrImperator".to_string(),
+            state: "opened".to_string(),
+            created_at: Utc::now(),
+            author: Author {
+                username: "BobrImperator".to_string(),
+                web_url: "https://gitlab.com/BobrImperator".to_string(),
+            },
+        };
+
+        assert_eq!(None, item_from_merge_request(merge_request));
+    }
+}
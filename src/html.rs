@@ -0,0 +1,43 @@
+use pulldown_cmark::{escape::escape_html, html::push_html, Options, Parser};
+
+/// Converts a markdown document to HTML, resolving the reference-style links.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    push_html(&mut html, Parser::new_ext(markdown, Options::empty()));
+
+    html
+}
+
+/// Wraps the HTML of a report in a standalone page.
+pub fn page(title: &str, body: &str) -> String {
+    let mut escaped_title = String::new();
+    escape_html(&mut escaped_title, title).unwrap_or_default();
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escaped_title, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_markdown_with_link_definitions() {
+        let markdown = "## Ember\n\n- [ember-engines/ember-engines] [#798](https://github.com/ember-engines/ember-engines/pull/798) Ember 4 compatibility ([@BobrImperator])\n\n[ember-engines/ember-engines]: https://github.com/ember-engines/ember-engines\n[@BobrImperator]: https://github.com/BobrImperator\n";
+
+        assert_eq!(
+            "<h2>Ember</h2>\n<ul>\n<li><a href=\"https://github.com/ember-engines/ember-engines\">ember-engines/ember-engines</a> <a href=\"https://github.com/ember-engines/ember-engines/pull/798\">#798</a> Ember 4 compatibility (<a href=\"https://github.com/BobrImperator\">@BobrImperator</a>)</li>\n</ul>\n",
+            markdown_to_html(markdown)
+        );
+    }
+
+    #[test]
+    fn it_wraps_html_in_a_page() {
+        assert_eq!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Ember &amp; Rust</title>\n</head>\n<body>\n<h2>Ember</h2>\n</body>\n</html>\n",
+            page("Ember & Rust", "<h2>Ember</h2>\n")
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use crate::cli::Args;
+use crate::error::AppError;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+pub const DEFAULT_CONFIG_PATH: &str = "twios.json";
+
+/// A config with a couple of example labels, meant to be edited.
+fn starter_config(users: &[String]) -> Value {
+    json!({
+        "users": users,
+        "header": ["This week our team contributed to the following projects:", ""],
+        "labels": [
+            { "name": "Ember", "repos": ["emberjs/ember.js", "ember-cli/ember-cli"] },
+            { "name": "Rust", "repos": ["rust-lang/crates.io"] }
+        ],
+        "exclude": []
+    })
+}
+
+/// Formats the config like the file extension, `.toml`, `.yaml` or `.yml`, and as JSON otherwise.
+fn format_config(path: &Path, config: &Value) -> Result<String, String> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        // `toml::Value` writes plain values before tables, which TOML requires.
+        Some("toml") => toml::Value::try_from(config)
+            .and_then(|config| toml::to_string_pretty(&config))
+            .map_err(|error| error.to_string()),
+        Some("yaml") | Some("yml") => {
+            serde_yaml::to_string(config).map_err(|error| error.to_string())
+        }
+        _ => serde_json::to_string_pretty(config)
+            .map(|config| format!("{}\n", config))
+            .map_err(|error| error.to_string()),
+    }
+}
+
+fn prompt_users() -> Vec<String> {
+    print!("GitHub users, comma separated: ");
+    let _ = io::stdout().flush();
+
+    let mut line = String::new();
+    let _ = io::stdin().lock().read_line(&mut line);
+
+    parse_users(&line)
+}
+
+fn parse_users(line: &str) -> Vec<String> {
+    line.split(',')
+        .map(|user| user.trim().to_string())
+        .filter(|user| !user.is_empty())
+        .collect()
+}
+
+/// Writes a starter config to the first `--config-path`, asking for the users when they
+/// weren't passed with `--users` and stdin is a terminal. Existing files are kept.
+pub fn write_starter_config(args: &Args) -> Result<(), AppError> {
+    let path = Path::new(
+        args.config_paths
+            .first()
+            .map(|path| path.as_str())
+            .unwrap_or(DEFAULT_CONFIG_PATH),
+    );
+    if path.exists() {
+        return Err(AppError::Write(format!(
+            "{} already exists, remove it or pass another --config-path.",
+            path.display()
+        )));
+    }
+
+    let users = if args.users.is_empty() && io::stdin().is_terminal() {
+        prompt_users()
+    } else {
+        args.users.clone()
+    };
+
+    let config = format_config(path, &starter_config(&users)).map_err(AppError::Write)?;
+    fs::write(path, config).map_err(|error| {
+        AppError::Write(format!("Could not write {}: {}", path.display(), error))
+    })?;
+
+    eprintln!(
+        "Wrote {}, edit its labels to group your PRs.",
+        path.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_prompted_users() {
+        assert_eq!(
+            vec!["BobrImperator".to_string(), "mansona".to_string()],
+            parse_users(" BobrImperator, mansona,,\n")
+        );
+    }
+
+    #[test]
+    fn it_formats_starter_configs_that_can_be_read_back() {
+        let config = starter_config(&["BobrImperator".to_string()]);
+
+        for path in ["twios.json", "twios.toml", "twios.yaml"] {
+            let contents = format_config(Path::new(path), &config).unwrap();
+            let value: Value = match path {
+                "twios.toml" => toml::from_str(&contents).unwrap(),
+                "twios.yaml" => serde_yaml::from_str(&contents).unwrap(),
+                _ => serde_json::from_str(&contents).unwrap(),
+            };
+
+            assert_eq!(config, value);
+        }
+    }
+}
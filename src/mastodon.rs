@@ -0,0 +1,207 @@
+use crate::Item;
+use reqwest::header::CONTENT_TYPE;
+use serde_json::{json, Value};
+
+// Mastodon counts every link as 23 characters, whatever its length.
+const URL_LENGTH: usize = 23;
+
+/// The length of a status the way Mastodon counts it.
+fn status_length(status: &str) -> usize {
+    let urls = status
+        .split_whitespace()
+        .filter(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .collect::<Vec<&str>>();
+
+    status.chars().count() + urls.len() * URL_LENGTH
+        - urls.iter().map(|url| url.chars().count()).sum::<usize>()
+}
+
+fn format_item(item: &Item) -> String {
+    format!(
+        "• {} by {} {}",
+        item.issue_title,
+        item.user_name.as_ref().unwrap_or(&item.user_login),
+        item.issue_url
+    )
+}
+
+/// Shortens the title of an item that doesn't fit into a status of its section on its own, so
+/// its link stays whole.
+fn fit_item(item: &Item, heading: &str, max_characters: usize) -> String {
+    let line = format_item(item);
+    let overflow =
+        status_length(&format!("{} (continued)\n{}", heading, line)).saturating_sub(max_characters);
+    if overflow == 0 {
+        return line;
+    }
+
+    let title_length = item
+        .issue_title
+        .chars()
+        .count()
+        .saturating_sub(overflow + 1);
+    let mut item = item.clone();
+    item.issue_title = format!(
+        "{}…",
+        item.issue_title
+            .chars()
+            .take(title_length)
+            .collect::<String>()
+    );
+
+    format_item(&item)
+}
+
+/// Builds the statuses of a thread, the title first and then the items of each section, split
+/// when a status would be longer than `max_characters` or have more than `items_per_status` items.
+pub fn thread(
+    title: &str,
+    sections: &[(String, Vec<Item>)],
+    max_characters: usize,
+    items_per_status: Option<usize>,
+) -> Vec<String> {
+    let mut statuses = vec![title.to_string()];
+
+    for (heading, items) in sections {
+        let mut status = heading.clone();
+        let mut count = 0;
+
+        for item in items {
+            let line = fit_item(item, heading, max_characters);
+            let is_full = items_per_status.is_some_and(|limit| count >= limit)
+                || status_length(&format!("{}\n{}", status, line)) > max_characters;
+            if count > 0 && is_full {
+                statuses.push(status);
+                status = format!("{} (continued)", heading);
+                count = 0;
+            }
+
+            status.push('\n');
+            status.push_str(&line);
+            count += 1;
+        }
+
+        statuses.push(status);
+    }
+
+    statuses
+}
+
+/// Posts the statuses as a thread, each one replying to the previous one.
+pub async fn post_thread(
+    instance_url: &str,
+    access_token: &str,
+    visibility: Option<&str>,
+    statuses: &[String],
+) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/v1/statuses", instance_url.trim_end_matches('/'));
+    let mut in_reply_to_id: Option<String> = None;
+
+    for status in statuses {
+        let mut body = json!({ "status": status });
+        if let Some(visibility) = visibility {
+            body["visibility"] = Value::from(visibility);
+        }
+        if let Some(id) = &in_reply_to_id {
+            body["in_reply_to_id"] = Value::from(id.as_str());
+        }
+
+        let response = client
+            .post(&url)
+            .bearer_auth(access_token)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|error| error.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("{} responded with {}", url, response.status()));
+        }
+
+        let response = response.text().await.map_err(|error| error.to_string())?;
+        let posted: Value = serde_json::from_str(&response).map_err(|error| error.to_string())?;
+        in_reply_to_id = posted["id"].as_str().map(String::from);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ItemKind, ItemMergeStatus, ItemRole};
+
+    fn item_helper(number: usize) -> Item {
+        Item {
+            issue_number: number.to_string(),
+            issue_title: "Update nan".to_string(),
+            issue_url: format!("https://github.com/atom/keyboard-layout/pull/{}", number),
+            organization_name: "atom".to_string(),
+            repository_name: "keyboard-layout".to_string(),
+            full_repository_name: "atom/keyboard-layout".to_string(),
+            original_repository_name: None,
+            repository_url: "https://github.com/atom/keyboard-layout".to_string(),
+            user_login: "mansona".to_string(),
+            user_url: "https://github.com/mansona".to_string(),
+            user_name: None,
+            state: "closed".to_string(),
+            merge_status: ItemMergeStatus::Merged,
+            role: ItemRole::Author,
+            kind: ItemKind::PullRequest,
+            created_at: "2021-12-01T10:00:00Z".parse().unwrap(),
+            co_authors: vec![],
+        }
+    }
+
+    #[test]
+    fn it_counts_urls_as_23_characters() {
+        assert_eq!(47, status_length(&format_item(&item_helper(63))));
+    }
+
+    #[test]
+    fn it_builds_a_status_per_section() {
+        let sections = vec![
+            ("Atom".to_string(), vec![item_helper(1), item_helper(2)]),
+            ("Ember".to_string(), vec![item_helper(3)]),
+        ];
+
+        assert_eq!(
+            vec![
+                "This week in open source".to_string(),
+                "Atom\n• Update nan by mansona https://github.com/atom/keyboard-layout/pull/1\n• Update nan by mansona https://github.com/atom/keyboard-layout/pull/2".to_string(),
+                "Ember\n• Update nan by mansona https://github.com/atom/keyboard-layout/pull/3".to_string(),
+            ],
+            thread("This week in open source", &sections, 500, None)
+        );
+    }
+
+    #[test]
+    fn it_splits_sections_into_several_statuses() {
+        let sections = vec![("Atom".to_string(), (1..=5).map(item_helper).collect())];
+
+        let statuses = thread("This week", &sections, 500, Some(2));
+        assert_eq!(4, statuses.len());
+        assert!(statuses[2].starts_with("Atom (continued)\n"));
+
+        let statuses = thread("This week", &sections, 100, None);
+        assert_eq!(5, statuses.len());
+        for status in statuses {
+            assert!(status_length(&status) <= 100);
+        }
+    }
+
+    #[test]
+    fn it_shortens_titles_of_items_longer_than_a_status() {
+        let mut item = item_helper(1);
+        item.issue_title = "a".repeat(100);
+        let sections = vec![("Atom".to_string(), vec![item])];
+
+        let statuses = thread("This week", &sections, 80, None);
+        assert_eq!(2, statuses.len());
+        assert!(status_length(&statuses[1]) <= 80);
+        assert!(
+            statuses[1].ends_with("… by mansona https://github.com/atom/keyboard-layout/pull/1")
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::time::Duration;
+
+// Webhooks are rate limited, e.g. Discord allows a few messages per second.
+const MAX_RETRIES: usize = 3;
+
+/// Posts each message as JSON to the webhook, stopping at the first one that fails.
+/// Rate limited messages are posted again after the `Retry-After` seconds.
+pub async fn post_messages(webhook_url: &str, messages: &[Value]) -> Result<(), String> {
+    let client = reqwest::Client::new();
+
+    for message in messages {
+        let mut retries = 0;
+        let response = loop {
+            let response = client
+                .post(webhook_url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(message.to_string())
+                .send()
+                .await
+                .map_err(|error| error.to_string())?;
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS || retries == MAX_RETRIES {
+                break response;
+            }
+
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .unwrap_or(1.0);
+            tokio::time::sleep(Duration::from_secs_f64(retry_after)).await;
+            retries += 1;
+        };
+
+        if !response.status().is_success() {
+            return Err(format!("The webhook responded with {}", response.status()));
+        }
+    }
+
+    Ok(())
+}
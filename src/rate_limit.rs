@@ -0,0 +1,522 @@
+use chrono::Utc;
+use octocrab::Octocrab;
+use reqwest::header::{HeaderMap, AUTHORIZATION, RETRY_AFTER};
+use reqwest::{RequestBuilder, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use std::sync;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Held while a request is paused, so every request waits until it continues.
+static PAUSE: Mutex<()> = Mutex::const_new(());
+
+/// The rate of each resource of the client's own token, recorded from the responses of `send`.
+static RATES: sync::Mutex<BTreeMap<&'static str, Rate>> = sync::Mutex::new(BTreeMap::new());
+
+/// The tokens requests rotate between, empty with a single token.
+static TOKENS: sync::Mutex<TokenPool> = sync::Mutex::new(TokenPool { tokens: vec![] });
+
+const MAX_RETRIES: u32 = 5;
+/// GitHub asks to wait at least a minute after a secondary rate limit without a `Retry-After`.
+const SECONDARY_BACKOFF_SECONDS: u64 = 60;
+const BACKOFF_SECONDS: u64 = 2;
+
+pub enum Resource {
+    Core,
+    Search,
+    GraphQl,
+}
+
+impl Resource {
+    /// The name GitHub sends in the `x-ratelimit-resource` header.
+    fn name(&self) -> &'static str {
+        match self {
+            Resource::Core => "core",
+            Resource::Search => "search",
+            Resource::GraphQl => "graphql",
+        }
+    }
+
+    /// Leaves some room for requests that are already in flight.
+    fn threshold(&self) -> u64 {
+        match self {
+            Resource::Core => 50,
+            Resource::Search => 5,
+            Resource::GraphQl => 50,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Rate {
+    remaining: u64,
+    reset: i64,
+}
+
+/// Pauses until the rate limit resets when the remaining requests of the previous responses
+/// dropped below the threshold, or until another request that is paused continues.
+/// The rate limit of the client's own token doesn't matter when rotating between several of them.
+pub async fn wait(resource: Resource) {
+    if !TOKENS.lock().unwrap().tokens.is_empty() {
+        return;
+    }
+
+    let _pause = match PAUSE.try_lock() {
+        Ok(_) if recorded_pause(&resource).is_none() => return,
+        Ok(pause) => pause,
+        Err(_) => PAUSE.lock().await,
+    };
+
+    if let Some((remaining, pause)) = recorded_pause(&resource) {
+        eprintln!(
+            "{} requests left, pausing for {}s until the rate limit resets.",
+            remaining,
+            pause.as_secs()
+        );
+        tokio::time::sleep(pause).await;
+    }
+}
+
+fn recorded_pause(resource: &Resource) -> Option<(u64, Duration)> {
+    let rate = *RATES.lock().unwrap().get(resource.name())?;
+
+    pause_duration(&rate, resource.threshold(), Utc::now().timestamp())
+        .map(|pause| (rate.remaining, pause))
+}
+
+/// A rate whose reset has passed doesn't pause anymore, even before the next response updates it.
+fn pause_duration(rate: &Rate, threshold: u64, now: i64) -> Option<Duration> {
+    if rate.remaining >= threshold || rate.reset < now {
+        return None;
+    }
+
+    Some(Duration::from_secs((rate.reset - now).max(0) as u64 + 1))
+}
+
+struct Token {
+    value: String,
+    /// The remaining requests and the reset of each resource the token was used for.
+    rates: HashMap<String, (u64, i64)>,
+}
+
+struct TokenPool {
+    tokens: Vec<Token>,
+}
+
+impl TokenPool {
+    /// The token with the most remaining requests for the resource, together with whether it has
+    /// any left. Tokens that weren't used for it yet or whose rate limit was reset come first.
+    fn pick(&self, resource: &str, now: i64) -> Option<(usize, bool)> {
+        self.tokens
+            .iter()
+            .enumerate()
+            .map(|(index, token)| match token.rates.get(resource) {
+                Some((remaining, reset)) if *reset > now => (index, *remaining),
+                _ => (index, u64::MAX),
+            })
+            .rev()
+            .max_by_key(|(_, remaining)| *remaining)
+            .map(|(index, remaining)| (index, remaining > 0))
+    }
+
+    fn update(&mut self, index: usize, resource: &str, remaining: u64, reset: i64) {
+        if let Some(token) = self.tokens.get_mut(index) {
+            token.rates.insert(resource.to_string(), (remaining, reset));
+        }
+    }
+}
+
+/// Rotates the requests sent with `send` between the tokens, a single token is left to the client.
+pub fn set_tokens(tokens: &[String]) {
+    let tokens = if tokens.len() > 1 {
+        tokens
+            .iter()
+            .map(|value| Token {
+                value: value.clone(),
+                rates: HashMap::new(),
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+
+    TOKENS.lock().unwrap().tokens = tokens;
+}
+
+/// A response read completely, so its body can be inspected before deciding to retry.
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// Sends the request `build` returns, retrying with exponential backoff when GitHub rejects it
+/// because of a secondary rate limit or abuse detection, fails with a server error or the
+/// connection fails. Other requests pause until the retry is sent.
+/// Rate limited requests are sent again right away with another token that has requests left.
+pub async fn send(
+    octocrab: &Octocrab,
+    resource: Resource,
+    build: impl Fn() -> RequestBuilder,
+) -> Result<Response, String> {
+    let mut attempt = 0;
+
+    loop {
+        let token = pick_token(&resource);
+        let request = match &token {
+            Some((_, value)) => build().header(AUTHORIZATION, format!("token {}", value)),
+            None => build(),
+        };
+
+        let delay = match read(octocrab, request).await {
+            Ok(response) => {
+                let now = Utc::now().timestamp();
+                let delay = retry_delay(&response, attempt, now);
+                if token.is_none() {
+                    record(&resource, &response.headers, now);
+                }
+                if let Some((index, _)) = token {
+                    if let Some(next) = rotate(index, &resource, &response, delay, now) {
+                        eprintln!(
+                            "Token {} is rate limited, switching to token {}.",
+                            index + 1,
+                            next + 1
+                        );
+                        continue;
+                    }
+                }
+
+                match delay {
+                    Some(delay) if attempt < MAX_RETRIES => {
+                        eprintln!(
+                            "GitHub responded with {}, retrying in {}s.",
+                            response.status,
+                            delay.as_secs()
+                        );
+                        delay
+                    }
+                    _ => return Ok(response),
+                }
+            }
+            Err(error) if attempt < MAX_RETRIES => {
+                let delay = backoff(BACKOFF_SECONDS, attempt);
+                eprintln!(
+                    "The request to GitHub failed, retrying in {}s: {}",
+                    delay.as_secs(),
+                    error
+                );
+                delay
+            }
+            Err(error) => return Err(error),
+        };
+
+        let _pause = PAUSE.lock().await;
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+fn pick_token(resource: &Resource) -> Option<(usize, String)> {
+    let pool = TOKENS.lock().unwrap();
+    let (index, _) = pool.pick(resource.name(), Utc::now().timestamp())?;
+
+    Some((index, pool.tokens[index].value.clone()))
+}
+
+fn record(resource: &Resource, headers: &HeaderMap, now: i64) {
+    if let Some((remaining, reset)) = rate(headers, None, now) {
+        RATES
+            .lock()
+            .unwrap()
+            .insert(resource.name(), Rate { remaining, reset });
+    }
+}
+
+/// Records the rate of the token that sent the response, returning another token with requests
+/// left when this one was rate limited.
+fn rotate(
+    index: usize,
+    resource: &Resource,
+    response: &Response,
+    delay: Option<Duration>,
+    now: i64,
+) -> Option<usize> {
+    let is_rate_limited = delay.is_some()
+        && (response.status == StatusCode::FORBIDDEN
+            || response.status == StatusCode::TOO_MANY_REQUESTS);
+    // Server errors are retried too, but don't say anything about the rate of the token.
+    let rate_limit_delay = delay.filter(|_| is_rate_limited);
+
+    let mut pool = TOKENS.lock().unwrap();
+    if let Some((remaining, reset)) = rate(&response.headers, rate_limit_delay, now) {
+        pool.update(index, resource.name(), remaining, reset);
+    }
+
+    match pool.pick(resource.name(), now) {
+        Some((next, true)) if is_rate_limited && next != index => Some(next),
+        _ => None,
+    }
+}
+
+async fn read(octocrab: &Octocrab, request: RequestBuilder) -> Result<Response, String> {
+    let response = octocrab
+        .execute(request)
+        .await
+        .map_err(|error| error.to_string())?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text().await.map_err(|error| error.to_string())?;
+
+    Ok(Response {
+        status,
+        headers,
+        body,
+    })
+}
+
+/// The remaining requests and the reset of the token that sent the response, a rate limited token
+/// counts as exhausted until it may retry.
+fn rate(headers: &HeaderMap, delay: Option<Duration>, now: i64) -> Option<(u64, i64)> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let remaining = header("x-ratelimit-remaining").and_then(|value| value.parse().ok());
+    let reset = header("x-ratelimit-reset").and_then(|value| value.parse().ok());
+
+    match (remaining, reset, delay) {
+        (_, _, Some(delay)) => Some((0, now + delay.as_secs() as i64)),
+        (Some(remaining), Some(reset), None) => Some((remaining, reset)),
+        _ => None,
+    }
+}
+
+fn backoff(base_seconds: u64, attempt: u32) -> Duration {
+    Duration::from_secs(base_seconds * 2u64.pow(attempt))
+}
+
+/// How long to wait before retrying the request, `None` when it shouldn't be retried.
+/// `Retry-After` and the reset of an exhausted rate limit take precedence over the backoff.
+fn retry_delay(response: &Response, attempt: u32, now: i64) -> Option<Duration> {
+    let header = |name: &str| {
+        response
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+
+    if response.status.is_server_error() {
+        return Some(backoff(BACKOFF_SECONDS, attempt));
+    }
+    if response.status != StatusCode::FORBIDDEN && response.status != StatusCode::TOO_MANY_REQUESTS
+    {
+        return None;
+    }
+
+    if let Some(seconds) = header(RETRY_AFTER.as_str()).and_then(|value| value.parse().ok()) {
+        return Some(Duration::from_secs(seconds));
+    }
+    if header("x-ratelimit-remaining") == Some("0") {
+        if let Some(reset) = header("x-ratelimit-reset").and_then(|value| value.parse::<i64>().ok())
+        {
+            return Some(Duration::from_secs((reset - now).max(0) as u64 + 1));
+        }
+    }
+
+    // A 403 is also returned for missing permissions, which retrying doesn't fix.
+    let body = response.body.to_lowercase();
+    if response.status == StatusCode::TOO_MANY_REQUESTS
+        || body.contains("secondary rate limit")
+        || body.contains("abuse")
+    {
+        return Some(backoff(SECONDARY_BACKOFF_SECONDS, attempt));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_does_not_pause_above_the_threshold() {
+        let rate = Rate {
+            remaining: 10,
+            reset: 1_700_000_060,
+        };
+
+        assert_eq!(None, pause_duration(&rate, 5, 1_700_000_000));
+    }
+
+    #[test]
+    fn it_pauses_until_the_reset_below_the_threshold() {
+        let rate = Rate {
+            remaining: 4,
+            reset: 1_700_000_060,
+        };
+
+        assert_eq!(
+            Some(Duration::from_secs(61)),
+            pause_duration(&rate, 5, 1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn it_does_not_pause_after_the_reset() {
+        let rate = Rate {
+            remaining: 4,
+            reset: 1_700_000_060,
+        };
+
+        assert_eq!(None, pause_duration(&rate, 5, 1_700_000_061));
+    }
+
+    fn response_helper(
+        status: StatusCode,
+        headers: &[(&'static str, &str)],
+        body: &str,
+    ) -> Response {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.insert(*name, value.parse().unwrap());
+        }
+
+        Response {
+            status,
+            headers: header_map,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_retries_after_the_retry_after_header() {
+        let response = response_helper(
+            StatusCode::FORBIDDEN,
+            &[("retry-after", "30")],
+            r#"{"message": "You have exceeded a secondary rate limit."}"#,
+        );
+
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            retry_delay(&response, 3, 1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn it_retries_after_the_rate_limit_resets() {
+        let response = response_helper(
+            StatusCode::FORBIDDEN,
+            &[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1700000060"),
+            ],
+            r#"{"message": "API rate limit exceeded"}"#,
+        );
+
+        assert_eq!(
+            Some(Duration::from_secs(61)),
+            retry_delay(&response, 0, 1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn it_backs_off_exponentially() {
+        let secondary_rate_limit = response_helper(
+            StatusCode::FORBIDDEN,
+            &[],
+            r#"{"message": "You have triggered an abuse detection mechanism."}"#,
+        );
+        let server_error = response_helper(StatusCode::BAD_GATEWAY, &[], "");
+
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            retry_delay(&secondary_rate_limit, 0, 1_700_000_000)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(240)),
+            retry_delay(&secondary_rate_limit, 2, 1_700_000_000)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(8)),
+            retry_delay(&server_error, 2, 1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn it_does_not_retry_other_responses() {
+        let forbidden = response_helper(
+            StatusCode::FORBIDDEN,
+            &[],
+            r#"{"message": "Resource not accessible by integration"}"#,
+        );
+        let not_found = response_helper(StatusCode::NOT_FOUND, &[], "");
+
+        assert_eq!(None, retry_delay(&forbidden, 0, 1_700_000_000));
+        assert_eq!(None, retry_delay(&not_found, 0, 1_700_000_000));
+    }
+
+    fn token_helper(value: &str, rates: &[(&str, u64, i64)]) -> Token {
+        Token {
+            value: value.to_string(),
+            rates: rates
+                .iter()
+                .map(|(resource, remaining, reset)| (resource.to_string(), (*remaining, *reset)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn it_picks_the_token_with_the_most_remaining_requests() {
+        let pool = TokenPool {
+            tokens: vec![
+                token_helper("ghp_a", &[("graphql", 100, 1_700_000_060)]),
+                token_helper("ghp_b", &[("graphql", 4000, 1_700_000_060)]),
+                token_helper("ghp_c", &[("core", 0, 1_700_000_060)]),
+            ],
+        };
+
+        assert_eq!(Some((2, true)), pool.pick("graphql", 1_700_000_000));
+        assert_eq!(Some((0, true)), pool.pick("core", 1_700_000_000));
+        assert_eq!(Some((0, true)), pool.pick("search", 1_700_000_000));
+        assert_eq!(
+            None,
+            TokenPool { tokens: vec![] }.pick("core", 1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn it_counts_tokens_as_exhausted_until_their_reset() {
+        let mut pool = TokenPool {
+            tokens: vec![token_helper("ghp_a", &[]), token_helper("ghp_b", &[])],
+        };
+        pool.update(0, "graphql", 0, 1_700_000_060);
+        pool.update(1, "graphql", 0, 1_700_000_030);
+
+        assert_eq!(Some((0, false)), pool.pick("graphql", 1_700_000_000));
+        assert_eq!(Some((1, true)), pool.pick("graphql", 1_700_000_030));
+    }
+
+    #[test]
+    fn it_reads_the_rate_of_responses() {
+        let response = response_helper(
+            StatusCode::OK,
+            &[
+                ("x-ratelimit-remaining", "4999"),
+                ("x-ratelimit-reset", "1700003600"),
+            ],
+            "",
+        );
+
+        assert_eq!(
+            Some((4999, 1_700_003_600)),
+            rate(&response.headers, None, 1_700_000_000)
+        );
+        assert_eq!(
+            Some((0, 1_700_000_060)),
+            rate(
+                &response.headers,
+                Some(Duration::from_secs(60)),
+                1_700_000_000
+            )
+        );
+        assert_eq!(None, rate(&HeaderMap::new(), None, 1_700_000_000));
+    }
+}
@@ -0,0 +1,259 @@
+use serde_json::{json, Value};
+
+fn strings(description: &str) -> Value {
+    json!({
+        "type": "array",
+        "items": { "type": "string" },
+        "description": description
+    })
+}
+
+fn templates() -> Value {
+    json!({
+        "type": "object",
+        "description": "Handlebars templates replacing the default format.",
+        "properties": {
+            "item": { "type": "string", "description": "Written for each item, e.g. - {{issue_title}} ({{credit}})." },
+            "label": { "type": "string", "description": "Written as the section heading, e.g. ## {{name}}." }
+        }
+    })
+}
+
+fn string_map(description: &str) -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": { "type": "string" },
+        "description": description
+    })
+}
+
+/// The JSON Schema of the config file, e.g. for the autocompletion of editors.
+pub fn config_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "this-week-in-open-source config",
+        "type": "object",
+        "required": ["labels"],
+        "properties": {
+            "labels": {
+                "type": "array",
+                "description": "Sections the PRs are grouped under.",
+                "items": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "repos": strings("Repositories listed under the label, e.g. simplabs/qunit-dom, emberjs/* or regex:^simplabs/.*-blueprint$."),
+                        "users": strings("Authors whose PRs are listed under the label."),
+                        "rules": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "repo": { "type": "string", "description": "org/repo where * matches any characters, or a regular expression starting with regex:." },
+                                    "title": { "type": "string", "description": "A word, or a regular expression starting with re:." },
+                                    "users": strings("Authors the rule matches.")
+                                }
+                            }
+                        },
+                        "templates": templates()
+                    }
+                }
+            },
+            "header": strings("Lines written before the sections, {date}, {count} and {contributors} are replaced."),
+            "users": strings("GitHub logins whose PRs are queried."),
+            "exclude": strings("Repositories left out of the report."),
+            "exclude_closed_not_merged": { "type": "boolean" },
+            "thanks_footer": { "type": "boolean" },
+            "output_style": {
+                "type": "object",
+                "properties": {
+                    "definitions_separator": { "type": "string" },
+                    "trailing_newline": { "type": "boolean" }
+                }
+            },
+            "highlights": strings("PR urls or repositories listed in a Highlights section."),
+            "title": { "type": "string" },
+            "rename": string_map("Repositories mapped to the name they're displayed with."),
+            "aliases": string_map("Old logins mapped to the current ones."),
+            "user_aliases": {
+                "type": "object",
+                "description": "Logins mapped to how they're credited in the report.",
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "description": "Credited instead of the login." },
+                        "url": { "type": "string", "description": "Linked instead of the GitHub profile." }
+                    },
+                    "required": ["name"]
+                }
+            },
+            "exclude_title_patterns": strings("Words, or regular expressions starting with re:, of PR titles to leave out."),
+            "tracked_repos": strings("Repositories whose merged PRs are listed regardless of their author."),
+            "include_issues": { "type": "boolean" },
+            "reviews_heading": { "type": "string" },
+            "only_merged": { "type": "boolean" },
+            "skip_drafts": { "type": "boolean" },
+            "skip_bots": { "type": "boolean", "description": "Leaves out PRs of bots, e.g. renovate or dependabot." },
+            "exclude_users": strings("Logins that aren't queried and whose PRs are left out."),
+            "auto_labels": {
+                "type": "object",
+                "description": "Labels of repositories that don't belong to any label.",
+                "properties": {
+                    "topics": string_map("GitHub topics mapped to a label name."),
+                    "languages": string_map("Primary languages mapped to a label name.")
+                }
+            },
+            "output_pattern": { "type": "string", "description": "{date}, {week} and {year} are replaced." },
+            "pending": strings("Repositories that still need a label, added by --update-config."),
+            "teams": {
+                "type": "object",
+                "additionalProperties": strings("The logins of the members."),
+                "description": "The members of each team, the sections of --group-by team."
+            },
+            "org": { "type": "string", "description": "The organization whose members are added to the users." },
+            "proxy": { "type": "string", "description": "The proxy of the requests unless HTTPS_PROXY or HTTP_PROXY are set." },
+            "templates": templates(),
+            "slack": {
+                "type": "object",
+                "description": "The incoming webhook the report is posted to by publish slack.",
+                "properties": {
+                    "webhook_url": { "type": "string", "description": "Defaults to the SLACK_WEBHOOK_URL environment variable." },
+                    "channel": { "type": "string" }
+                }
+            },
+            "discord": {
+                "type": "object",
+                "description": "The webhook the report is posted to by publish discord.",
+                "properties": {
+                    "webhook_url": { "type": "string", "description": "Defaults to the DISCORD_WEBHOOK_URL environment variable." }
+                }
+            },
+            "mastodon": {
+                "type": "object",
+                "description": "The account the report is posted as a thread with by publish mastodon.",
+                "properties": {
+                    "instance_url": { "type": "string" },
+                    "access_token": { "type": "string", "description": "Defaults to the MASTODON_ACCESS_TOKEN environment variable." },
+                    "visibility": { "enum": ["public", "unlisted", "private", "direct"] },
+                    "max_characters": { "type": "integer", "description": "The character limit of the instance, 500 by default." },
+                    "items_per_status": { "type": "integer" }
+                }
+            },
+            "sources": {
+                "type": "array",
+                "description": "The forges the PRs are fetched from, defaults to github and the ones with a config.",
+                "items": { "enum": ["github", "gitlab", "gitea"] }
+            },
+            "github": {
+                "type": "object",
+                "description": "The GitHub Enterprise Server instance queried instead of github.com, and the GitHub App installation authenticated as.",
+                "properties": {
+                    "api_url": { "type": "string", "description": "e.g. https://github.example.com/api/v3." },
+                    "web_url": { "type": "string", "description": "Defaults to api_url without /api/v3." },
+                    "app_id": { "type": "integer", "description": "Defaults to GITHUB_APP_ID." },
+                    "installation_id": { "type": "integer", "description": "Defaults to GITHUB_APP_INSTALLATION_ID." },
+                    "private_key_path": { "type": "string", "description": "The PEM file of the app, its contents default to GITHUB_APP_PRIVATE_KEY." }
+                }
+            },
+            "gitlab": {
+                "type": "object",
+                "description": "The GitLab instance whose merge requests of the users are added to the report.",
+                "properties": {
+                    "base_url": { "type": "string", "description": "Defaults to https://gitlab.com." },
+                    "token": { "type": "string", "description": "Defaults to the GITLAB_TOKEN environment variable." },
+                    "users": strings("GitLab usernames, defaults to users.")
+                }
+            },
+            "gitea": {
+                "type": "object",
+                "description": "The Gitea, Forgejo or Codeberg instance whose PRs of the users on the repos are added to the report.",
+                "properties": {
+                    "base_url": { "type": "string", "description": "Defaults to https://codeberg.org." },
+                    "token": { "type": "string", "description": "Defaults to the GITEA_TOKEN environment variable." },
+                    "repos": strings("Repositories whose PRs are queried."),
+                    "users": strings("Usernames on the instance, defaults to users.")
+                }
+            },
+            "front_matter": {
+                "type": "object",
+                "description": "Front matter written at the top of the report, e.g. for Hugo or Jekyll.",
+                "properties": {
+                    "format": { "enum": ["yaml", "toml"] },
+                    "title": { "type": "string", "description": "{date} is replaced, defaults to title." },
+                    "tags": strings("Tags of the post.")
+                }
+            }
+        }
+    })
+}
+
+/// Collects the paths of the keys of `value` that aren't properties in the `schema`,
+/// e.g. `labels[0].repoz`.
+pub fn find_unknown_keys(
+    value: &Value,
+    schema: &Value,
+    path: &str,
+    unknown_keys: &mut Vec<String>,
+) {
+    match value {
+        Value::Object(object) => {
+            let properties = match schema.get("properties").and_then(Value::as_object) {
+                Some(properties) => properties,
+                None => return,
+            };
+
+            for (key, value) in object {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+
+                match properties.get(key) {
+                    Some(schema) => find_unknown_keys(value, schema, &key_path, unknown_keys),
+                    None => unknown_keys.push(key_path),
+                }
+            }
+        }
+        Value::Array(values) => {
+            if let Some(schema) = schema.get("items") {
+                for (index, value) in values.iter().enumerate() {
+                    find_unknown_keys(value, schema, &format!("{}[{}]", path, index), unknown_keys);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_unknown_keys() {
+        let config = json!({
+            "labels": [
+                { "name": "Ember", "repos": [], "rules": [{ "repo": "emberjs/*", "titel": "fix" }] },
+                { "name": "Rust", "repoz": ["rust-lang/crates.io"] }
+            ],
+            "exlude": [],
+            "rename": { "atom/keyboard-layout": "atom/keymaps" },
+            "output_style": { "trailing_newline": true, "separator": "" }
+        });
+        let mut unknown_keys = vec![];
+
+        find_unknown_keys(&config, &config_schema(), "", &mut unknown_keys);
+
+        assert_eq!(
+            vec![
+                "exlude".to_string(),
+                "labels[0].rules[0].titel".to_string(),
+                "labels[1].repoz".to_string(),
+                "output_style.separator".to_string(),
+            ],
+            unknown_keys
+        );
+    }
+}
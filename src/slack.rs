@@ -0,0 +1,111 @@
+use crate::chat::{self, Syntax};
+use serde_json::{json, Value};
+
+// Slack rejects messages with more blocks than this, and longer texts.
+const MAX_BLOCKS: usize = 50;
+const MAX_SECTION_LENGTH: usize = 3000;
+const MAX_HEADER_LENGTH: usize = 150;
+
+fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+const MRKDWN: Syntax = Syntax {
+    link: |text, url| format!("<{}|{}>", url, text),
+    escape: escape_mrkdwn,
+    code: |code| format!("`{}`", escape_mrkdwn(code)),
+    strong: "*",
+    emphasis: "_",
+};
+
+fn header(text: &str) -> Value {
+    json!({
+        "type": "header",
+        "text": {
+            "type": "plain_text",
+            "text": chat::truncate(text, MAX_HEADER_LENGTH)
+        }
+    })
+}
+
+fn section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })
+}
+
+/// Converts a markdown document to Block Kit blocks, its headings become header blocks
+/// and everything in between mrkdwn sections with the links resolved.
+pub fn markdown_to_blocks(markdown: &str) -> Vec<Value> {
+    let mut blocks = vec![];
+
+    for section_text in chat::sections(markdown, &MRKDWN) {
+        if let Some(heading) = &section_text.heading {
+            blocks.push(header(heading));
+        }
+        for text in chat::split_text(&section_text.text, MAX_SECTION_LENGTH) {
+            blocks.push(section(&text));
+        }
+    }
+
+    blocks
+}
+
+/// Splits the blocks into as many webhook messages as needed, `text` is shown in notifications.
+pub fn messages(blocks: &[Value], text: &str, channel: Option<&str>) -> Vec<Value> {
+    blocks
+        .chunks(MAX_BLOCKS)
+        .map(|blocks| {
+            let mut message = json!({ "text": text, "blocks": blocks });
+            if let Some(channel) = channel {
+                message["channel"] = Value::from(channel);
+            }
+
+            message
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_markdown_to_blocks() {
+        let markdown = "## Ember & Glimmer\n\n- [ember-engines/ember-engines] [#798](https://github.com/ember-engines/ember-engines/pull/798) Ember 4 <compatibility> ([@BobrImperator])\n- [#1](https://github.com/simplabs/qunit-dom/pull/1) **Update** `nan`\n\nThanks!\n\n[ember-engines/ember-engines]: https://github.com/ember-engines/ember-engines\n[@BobrImperator]: https://github.com/BobrImperator\n";
+
+        assert_eq!(
+            vec![
+                header("Ember & Glimmer"),
+                section("• <https://github.com/ember-engines/ember-engines|ember-engines/ember-engines> <https://github.com/ember-engines/ember-engines/pull/798|#798> Ember 4 &lt;compatibility&gt; (<https://github.com/BobrImperator|@BobrImperator>)\n• <https://github.com/simplabs/qunit-dom/pull/1|#1> *Update* `nan`\n\nThanks!"),
+            ],
+            markdown_to_blocks(markdown)
+        );
+    }
+
+    #[test]
+    fn it_splits_long_sections() {
+        let line = format!("- {}\n", "a".repeat(1000));
+        let blocks = markdown_to_blocks(&line.repeat(4));
+
+        assert_eq!(2, blocks.len());
+        for block in blocks {
+            assert!(block["text"]["text"].as_str().unwrap().len() <= MAX_SECTION_LENGTH);
+        }
+    }
+
+    #[test]
+    fn it_splits_blocks_into_messages() {
+        let blocks = vec![section("a"); 51];
+        let messages = messages(&blocks, "This week", Some("#open-source"));
+
+        assert_eq!(2, messages.len());
+        assert_eq!(50, messages[0]["blocks"].as_array().unwrap().len());
+        assert_eq!(1, messages[1]["blocks"].as_array().unwrap().len());
+        assert_eq!("#open-source", messages[1]["channel"]);
+        assert_eq!("This week", messages[1]["text"]);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::cli::AppParams;
+use crate::error::AppError;
+use crate::{date_range, Item, ItemMergeStatus};
+use chrono::{Local, NaiveDate};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{self, StreamExt};
+
+/// A forge the PRs of the users are fetched from, selected with `sources`.
+pub trait Source: Sync {
+    /// The name used in messages, e.g. `GitLab`.
+    fn name(&self) -> &'static str;
+
+    /// The usernames on the forge, the `users` of the config are used when it's empty.
+    fn users(&self) -> &[String];
+
+    /// Fetches the PRs the user created in the date range, every day is included without one.
+    fn fetch_items<'a>(
+        &'a self,
+        user: &'a str,
+        range: Option<(NaiveDate, NaiveDate)>,
+    ) -> BoxFuture<'a, Result<Vec<Item>, AppError>>;
+
+    /// Fetches the PRs of up to `concurrency` users at once, returning them together with the number of users skipped
+    /// because of errors with `continue_on_error`.
+    fn fetch_all_items<'a>(
+        &'a self,
+        app_params: &'a AppParams,
+    ) -> BoxFuture<'a, Result<(Vec<Item>, usize), AppError>> {
+        async move {
+            let users = if self.users().is_empty() {
+                &app_params.users
+            } else {
+                self.users()
+            };
+            let range = date_range(
+                &app_params.date,
+                &app_params.date_sign,
+                Local::now().date_naive(),
+            );
+            let mut items: Vec<Item> = vec![];
+            let mut skipped_count = 0;
+
+            let requests = users
+                .iter()
+                .map(|user| self.fetch_items(user, range))
+                .collect::<Vec<_>>();
+            let results = stream::iter(requests)
+                .buffered(app_params.concurrency)
+                .collect::<Vec<_>>()
+                .await;
+
+            for (user, result) in users.iter().zip(results) {
+                match result {
+                    Ok(mut user_items) => items.append(&mut user_items),
+                    Err(error) if app_params.continue_on_error => {
+                        eprintln!(
+                            "Skipping {} on {} because of an error: {}",
+                            user,
+                            self.name(),
+                            error
+                        );
+                        skipped_count += 1;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+
+            Ok((items, skipped_count))
+        }
+        .boxed()
+    }
+
+    /// Looks up what the fetched items are missing and leaves out the ones the options exclude,
+    /// the items of most forges come with their merge status.
+    fn complete_items<'a>(
+        &'a self,
+        items: Vec<Item>,
+        app_params: &'a AppParams,
+    ) -> BoxFuture<'a, Vec<Item>> {
+        async move {
+            items
+                .into_iter()
+                .filter(|item| {
+                    !app_params.only_merged || item.merge_status == ItemMergeStatus::Merged
+                })
+                .collect()
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,143 @@
+use crate::Report;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+pub const STATE_PATH: &str = ".twios-state.json";
+pub const WATERMARKS_PATH: &str = ".twios-watermarks.json";
+pub const LAST_FETCH_PATH: &str = ".twios-last-fetch.json";
+
+/// Maps `org/repo` to the number of the newest PR featured in a report.
+pub type Watermarks = HashMap<String, u64>;
+
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RunState {
+    pub last_run: String,
+}
+
+impl RunState {
+    pub fn today() -> RunState {
+        RunState {
+            last_run: Local::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+pub fn read_state<P: AsRef<Path>>(path: P) -> Option<RunState> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+
+    serde_json::from_reader(reader).ok()
+}
+
+pub fn write_state<P: AsRef<Path>>(path: P, state: &RunState) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    let writer = BufWriter::new(file);
+
+    serde_json::to_writer_pretty(writer, state)?;
+
+    Ok(())
+}
+
+pub fn read_watermarks<P: AsRef<Path>>(path: P) -> Watermarks {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Watermarks::new(),
+    };
+    let reader = BufReader::new(file);
+
+    serde_json::from_reader(reader).unwrap_or_default()
+}
+
+pub fn write_watermarks<P: AsRef<Path>>(
+    path: P,
+    watermarks: &Watermarks,
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    let writer = BufWriter::new(file);
+
+    serde_json::to_writer_pretty(writer, watermarks)?;
+
+    Ok(())
+}
+
+pub fn read_last_fetch<P: AsRef<Path>>(path: P) -> Option<Report> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+
+    serde_json::from_reader(reader).ok()
+}
+
+pub fn write_last_fetch<P: AsRef<Path>>(path: P, report: &Report) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    let writer = BufWriter::new(file);
+
+    serde_json::to_writer(writer, report)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn it_reads_written_state() {
+        let path = env::temp_dir().join("twios-it-reads-written-state.json");
+        let state = RunState {
+            last_run: "2022-02-18".to_string(),
+        };
+
+        write_state(&path, &state).unwrap();
+
+        assert_eq!(Some(state), read_state(&path));
+    }
+
+    #[test]
+    fn it_reads_no_state_when_file_is_missing() {
+        assert_eq!(None, read_state("does/not/exist/.twios-state.json"));
+    }
+
+    #[test]
+    fn it_reads_written_watermarks() {
+        let path = env::temp_dir().join("twios-it-reads-written-watermarks.json");
+        let watermarks = Watermarks::from([("simplabs/ember-simple-auth".to_string(), 2311)]);
+
+        write_watermarks(&path, &watermarks).unwrap();
+
+        assert_eq!(watermarks, read_watermarks(&path));
+    }
+
+    #[test]
+    fn it_reads_no_watermarks_when_file_is_missing() {
+        assert!(read_watermarks("does/not/exist/.twios-watermarks.json").is_empty());
+    }
+
+    #[test]
+    fn it_reads_the_written_last_fetch() {
+        let path = env::temp_dir().join("twios-it-reads-the-written-last-fetch.json");
+        let report = Report {
+            windows: vec![crate::ReportWindow {
+                heading: Some("# Week of 2024-01-01".to_string()),
+                items: vec![],
+            }],
+            fetched_count: 3,
+            skipped_count: 1,
+        };
+
+        write_last_fetch(&path, &report).unwrap();
+        let last_fetch = read_last_fetch(&path).unwrap();
+
+        assert_eq!(1, last_fetch.windows.len());
+        assert_eq!(report.windows[0].heading, last_fetch.windows[0].heading);
+        assert_eq!(3, last_fetch.fetched_count);
+        assert_eq!(1, last_fetch.skipped_count);
+        assert!(read_last_fetch("does/not/exist/.twios-last-fetch.json").is_none());
+    }
+}
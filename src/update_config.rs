@@ -0,0 +1,347 @@
+use crate::error::AppError;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Where `--update-config` appends the unknown repositories to.
+#[derive(Debug, PartialEq)]
+pub enum Target<'a> {
+    Label(&'a str),
+    /// The `pending` list, for repositories that still need a label.
+    Pending,
+}
+
+fn skip_whitespace(contents: &[u8], mut index: usize) -> usize {
+    while index < contents.len() && contents[index].is_ascii_whitespace() {
+        index += 1;
+    }
+
+    index
+}
+
+/// The index after the closing quote of the string starting at `index`.
+fn string_end(contents: &[u8], mut index: usize) -> Option<usize> {
+    index += 1;
+    while index < contents.len() {
+        match contents[index] {
+            b'\\' => index += 2,
+            b'"' => return Some(index + 1),
+            _ => index += 1,
+        }
+    }
+
+    None
+}
+
+/// The index after the value starting at `index`, the file is known to be valid JSON.
+fn value_end(contents: &[u8], index: usize) -> Option<usize> {
+    match contents.get(index)? {
+        b'"' => string_end(contents, index),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut index = index;
+            while index < contents.len() {
+                match contents[index] {
+                    b'"' => {
+                        index = string_end(contents, index)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(index + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                index += 1;
+            }
+
+            None
+        }
+        _ => {
+            let mut index = index;
+            while index < contents.len()
+                && !matches!(contents[index], b',' | b'}' | b']')
+                && !contents[index].is_ascii_whitespace()
+            {
+                index += 1;
+            }
+
+            Some(index)
+        }
+    }
+}
+
+/// The spans of the values of an array or object starting at `open`, with the index of its
+/// closing bracket. Object members are returned with the span of their key.
+fn members(contents: &[u8], open: usize) -> Option<(Vec<(usize, usize)>, usize)> {
+    let mut members = vec![];
+    let mut index = skip_whitespace(contents, open + 1);
+
+    while !matches!(contents.get(index)?, b'}' | b']') {
+        let start = index;
+        let mut end = value_end(contents, index)?;
+        if contents[open] == b'{' {
+            let colon = skip_whitespace(contents, end);
+            end = value_end(contents, skip_whitespace(contents, colon + 1))?;
+        }
+        members.push((start, end));
+
+        index = skip_whitespace(contents, end);
+        if contents.get(index)? == &b',' {
+            index = skip_whitespace(contents, index + 1);
+        }
+    }
+
+    Some((members, index))
+}
+
+/// The index of the value of the key in the object starting at `open`.
+fn find_key(contents: &[u8], open: usize, key: &str) -> Option<usize> {
+    let (members, _) = members(contents, open)?;
+
+    members.into_iter().find_map(|(start, _)| {
+        let key_end = string_end(contents, start)?;
+        let name: String = serde_json::from_slice(&contents[start..key_end]).ok()?;
+        let colon = skip_whitespace(contents, key_end);
+        (name == key).then(|| skip_whitespace(contents, colon + 1))
+    })
+}
+
+/// Inserts the values after the last member of the array or object starting at `open`,
+/// separated like its first member, e.g. on their own lines with the same indentation.
+fn insert_members(contents: &str, open: usize, values: &[String]) -> Option<String> {
+    let bytes = contents.as_bytes();
+    let (members, close) = members(bytes, open)?;
+
+    let (position, text) = match (members.first(), members.last()) {
+        (Some((first, _)), Some((_, last))) => {
+            let separator = match &contents[open + 1..*first] {
+                "" => " ",
+                separator => separator,
+            };
+            let text = values
+                .iter()
+                .map(|value| format!(",{}{}", separator, value))
+                .collect::<String>();
+            (*last, text)
+        }
+        _ => (close, values.join(", ")),
+    };
+
+    Some(format!(
+        "{}{}{}",
+        &contents[..position],
+        text,
+        &contents[position..]
+    ))
+}
+
+/// Appends the repositories to the `repos` of the label or to `pending`, leaving the rest of
+/// the file as it is. Lists and keys that don't exist yet are added.
+pub fn append_repos(contents: &str, target: &Target, repos: &[String]) -> Result<String, String> {
+    if !serde_json::from_str::<Value>(contents).is_ok_and(|config| config.is_object()) {
+        return Err(String::from("The config isn't a JSON object."));
+    }
+    let bytes = contents.as_bytes();
+    let root = skip_whitespace(bytes, 0);
+    let repos = repos
+        .iter()
+        .map(|repo| serde_json::to_string(repo).unwrap_or_default())
+        .collect::<Vec<String>>();
+
+    let (object, key) = match target {
+        Target::Pending => (root, "pending"),
+        Target::Label(name) => {
+            let labels = find_key(bytes, root, "labels")
+                .ok_or_else(|| String::from("The config doesn't have labels."))?;
+            let (labels, _) = members(bytes, labels).unwrap_or_default();
+            let label = labels
+                .into_iter()
+                .map(|(start, _)| start)
+                .find(|start| {
+                    find_key(bytes, *start, "name").is_some_and(|value| {
+                        value_end(bytes, value)
+                            .and_then(|end| {
+                                serde_json::from_slice::<String>(&bytes[value..end]).ok()
+                            })
+                            .is_some_and(|label_name| label_name == *name)
+                    })
+                })
+                .ok_or_else(|| format!("The config doesn't have the label {}.", name))?;
+            (label, "repos")
+        }
+    };
+
+    let updated = match find_key(bytes, object, key) {
+        Some(list) if bytes.get(list) == Some(&b'[') => insert_members(contents, list, &repos),
+        Some(_) => return Err(format!("{} of the config isn't a list.", key)),
+        None => insert_members(
+            contents,
+            object,
+            &[format!("\"{}\": [{}]", key, repos.join(", "))],
+        ),
+    };
+
+    updated.ok_or_else(|| String::from("The config isn't valid JSON."))
+}
+
+fn defines_label(path: &str, name: &str) -> bool {
+    let config = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Value>(&contents).ok());
+
+    config
+        .as_ref()
+        .and_then(|config| config["labels"].as_array())
+        .is_some_and(|labels| labels.iter().any(|label| label["name"] == *name))
+}
+
+/// The config file of the label, or the first one for `pending`.
+fn config_path<'a>(config_paths: &'a [String], target: &Target) -> Option<&'a String> {
+    let defines_label = |path: &&String| match target {
+        Target::Label(name) => defines_label(path, name),
+        Target::Pending => false,
+    };
+
+    config_paths
+        .iter()
+        .find(defines_label)
+        .or_else(|| config_paths.first())
+}
+
+/// Appends the repositories to the config file of `--update-config`, which has to be JSON since
+/// TOML and YAML can't be edited without reformatting them.
+pub fn update_config_file(
+    config_paths: &[String],
+    target: &Target,
+    repos: &[String],
+) -> Result<(), AppError> {
+    let path = config_path(config_paths, target)
+        .ok_or_else(|| AppError::Usage(String::from("--update-config needs a --config-path.")))?;
+    if matches!(
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str()),
+        Some("toml") | Some("yaml") | Some("yml")
+    ) {
+        return Err(AppError::Usage(format!(
+            "--update-config can only update JSON config files, not {}.",
+            path
+        )));
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|error| AppError::ConfigNotFound(format!("{}: {}", path, error)))?;
+    let updated = append_repos(&contents, target, repos)
+        .map_err(|error| AppError::ConfigParse(format!("{}: {}", path, error)))?;
+
+    fs::write(path, updated).map_err(|error| {
+        AppError::Write(format!("Could not update the config {}: {}", path, error))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repos_helper() -> Vec<String> {
+        vec![
+            "simplabs/qunit-dom".to_string(),
+            "rust-lang/crates.io".to_string(),
+        ]
+    }
+
+    #[test]
+    fn it_appends_repos_to_labels() {
+        let config = r#"{
+  "labels": [
+    {
+      "name": "Ember",
+      "repos": [
+        "emberjs/ember.js"
+      ]
+    },
+    { "name": "Rust", "repos": ["rust-lang/cargo"] }
+  ]
+}
+"#;
+
+        assert_eq!(
+            r#"{
+  "labels": [
+    {
+      "name": "Ember",
+      "repos": [
+        "emberjs/ember.js",
+        "simplabs/qunit-dom",
+        "rust-lang/crates.io"
+      ]
+    },
+    { "name": "Rust", "repos": ["rust-lang/cargo"] }
+  ]
+}
+"#,
+            append_repos(config, &Target::Label("Ember"), &repos_helper()).unwrap()
+        );
+        assert_eq!(
+            r#"{
+  "labels": [
+    {
+      "name": "Ember",
+      "repos": [
+        "emberjs/ember.js"
+      ]
+    },
+    { "name": "Rust", "repos": ["rust-lang/cargo", "simplabs/qunit-dom", "rust-lang/crates.io"] }
+  ]
+}
+"#,
+            append_repos(config, &Target::Label("Rust"), &repos_helper()).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_adds_missing_lists() {
+        let config = r#"{
+  "labels": [{ "name": "Ember", "rules": [{ "repo": "emberjs/*" }] }],
+  "exclude": []
+}"#;
+
+        assert_eq!(
+            r#"{
+  "labels": [{ "name": "Ember", "rules": [{ "repo": "emberjs/*" }], "repos": ["simplabs/qunit-dom", "rust-lang/crates.io"] }],
+  "exclude": [],
+  "pending": ["simplabs/qunit-dom", "rust-lang/crates.io"]
+}"#,
+            append_repos(
+                &append_repos(config, &Target::Label("Ember"), &repos_helper()).unwrap(),
+                &Target::Pending,
+                &repos_helper()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            r#"{ "pending": ["simplabs/qunit-dom", "rust-lang/crates.io"] }"#,
+            append_repos(r#"{ "pending": [] }"#, &Target::Pending, &repos_helper()).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_fails_without_the_label() {
+        assert_eq!(
+            Err("The config doesn't have the label Go.".to_string()),
+            append_repos(
+                r#"{ "labels": [{ "name": "Ember, \"Glimmer\"", "repos": [] }] }"#,
+                &Target::Label("Go"),
+                &repos_helper()
+            )
+        );
+        assert_eq!(
+            Err("The config isn't a JSON object.".to_string()),
+            append_repos("[]", &Target::Pending, &repos_helper())
+        );
+    }
+}